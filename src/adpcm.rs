@@ -0,0 +1,112 @@
+use crate::alext::*;
+use crate::buffer::Buffer;
+use crate::error::{ensure, Error, Result};
+use crate::ext::{is_al_extension_present, require_al_extension};
+use crate::format::Channels;
+
+pub mod codec;
+
+/// ADPCM encodings openal-soft decodes natively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdpcmFormat {
+    /// IMA4 (`AL_EXT_IMA4`).
+    Ima4,
+    /// Microsoft ADPCM (`AL_SOFT_MSADPCM`).
+    MsAdpcm,
+}
+
+impl AdpcmFormat {
+    /// block alignment used when `AL_UNPACK_BLOCK_ALIGNMENT_SOFT` is not set, in sample frames.
+    pub fn default_block_frames(self) -> usize {
+        match self {
+            AdpcmFormat::Ima4 => 65,
+            AdpcmFormat::MsAdpcm => 64,
+        }
+    }
+
+    /// `true` if blocks of `block_frames` sample frames can be encoded in this format.
+    pub fn is_valid_block_frames(self, block_frames: usize) -> bool {
+        match self {
+            // a 4 byte header holding the first sample, then 8 samples per 4 bytes.
+            AdpcmFormat::Ima4 => block_frames >= 9 && (block_frames - 1) % 8 == 0,
+            // a 7 byte header holding the first 2 samples, then 2 samples per byte.
+            AdpcmFormat::MsAdpcm => block_frames >= 2 && block_frames % 2 == 0,
+        }
+    }
+
+    /// size in bytes of one block of `block_frames` sample frames.
+    pub fn block_bytes(self, channels: usize, block_frames: usize) -> usize {
+        match self {
+            AdpcmFormat::Ima4 => channels * (4 + (block_frames - 1) / 2),
+            AdpcmFormat::MsAdpcm => channels * (7 + (block_frames - 2) / 2),
+        }
+    }
+
+    // `block_bytes` after checking the channels and the block alignment.
+    pub(crate) fn checked_block_bytes(self, channels: Channels, block_frames: usize) -> Result<usize> {
+        ensure(
+            channels == Channels::Mono || channels == Channels::Stereo,
+            "ADPCM data must be mono or stereo",
+        )?;
+        ensure(
+            self.is_valid_block_frames(block_frames),
+            "invalid block alignment for the ADPCM format",
+        )?;
+        Ok(self.block_bytes(channels.count(), block_frames))
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            AdpcmFormat::Ima4 => "AL_EXT_IMA4\0",
+            AdpcmFormat::MsAdpcm => "AL_SOFT_MSADPCM\0",
+        }
+    }
+
+    fn al_format(self, channels: Channels) -> Option<ALenum> {
+        Some(match (self, channels) {
+            (AdpcmFormat::Ima4, Channels::Mono) => AL_FORMAT_MONO_IMA4,
+            (AdpcmFormat::Ima4, Channels::Stereo) => AL_FORMAT_STEREO_IMA4,
+            (AdpcmFormat::MsAdpcm, Channels::Mono) => AL_FORMAT_MONO_MSADPCM_SOFT,
+            (AdpcmFormat::MsAdpcm, Channels::Stereo) => AL_FORMAT_STEREO_MSADPCM_SOFT,
+            _ => return None,
+        } as ALenum)
+    }
+}
+
+impl Buffer {
+    /// uploads ADPCM blocks of `block_frames` sample frames each.
+    ///
+    /// a block alignment other than the format's default needs `AL_SOFT_block_alignment`. the
+    /// buffer's unpack alignment is reset to 0 (the format default) afterwards, even on failure.
+    pub fn adpcm_data(
+        &mut self,
+        format: AdpcmFormat,
+        channels: Channels,
+        block_frames: usize,
+        data: &[u8],
+        frequency: ALsizei,
+    ) -> Result<()> {
+        require_al_extension(format.extension())?;
+        let al_format = format
+            .al_format(channels)
+            .ok_or(Error::InvalidArgument("ADPCM data must be mono or stereo"))?;
+        let block_bytes = format.checked_block_bytes(channels, block_frames)?;
+        ensure(
+            !data.is_empty() && data.len() % block_bytes == 0,
+            "ADPCM data length must be a whole number of blocks",
+        )?;
+        let frames = data.len() / block_bytes * block_frames;
+
+        if !is_al_extension_present("AL_SOFT_block_alignment\0") {
+            if block_frames != format.default_block_frames() {
+                require_al_extension("AL_SOFT_block_alignment\0")?;
+            }
+            return self.data_compressed(al_format, data, frames, frequency);
+        }
+        self.set_i(AL_UNPACK_BLOCK_ALIGNMENT_SOFT, block_frames as ALint)?;
+        let result = self.data_compressed(al_format, data, frames, frequency);
+        // the alignment would otherwise apply to every later upload to this buffer, PCM included.
+        let reset = self.set_i(AL_UNPACK_BLOCK_ALIGNMENT_SOFT, 0);
+        result.and(reset)
+    }
+}
@@ -0,0 +1,414 @@
+//! encoder and decoder for the ADPCM block layouts openal-soft decodes.
+//!
+//! the decoders follow openal-soft's, so `decode(encode(pcm))` is exactly what plays back.
+
+use super::AdpcmFormat;
+use crate::error::{ensure, Result};
+use crate::format::Channels;
+
+const IMA_STEP_SIZE: [i32; 89] = [
+    7, 8, 9, 10, 11, 12, 13, 14, 16, 17, 19, 21, 23, 25, 28, 31, 34, 37, 41, 45, 50, 55, 60, 66, 73, 80, 88, 97, 107,
+    118, 130, 143, 157, 173, 190, 209, 230, 253, 279, 307, 337, 371, 408, 449, 494, 544, 598, 658, 724, 796, 876, 963,
+    1060, 1166, 1282, 1411, 1552, 1707, 1878, 2066, 2272, 2499, 2749, 3024, 3327, 3660, 4026, 4428, 4871, 5358, 5894,
+    6484, 7132, 7845, 8630, 9493, 10442, 11487, 12635, 13899, 15289, 16818, 18500, 20350, 22385, 24623, 27086, 29794,
+    32767,
+];
+
+const IMA_CODEWORD: [i32; 16] = [1, 3, 5, 7, 9, 11, 13, 15, -1, -3, -5, -7, -9, -11, -13, -15];
+
+const IMA_INDEX_ADJUST: [i32; 16] = [-1, -1, -1, -1, 2, 4, 6, 8, -1, -1, -1, -1, 2, 4, 6, 8];
+
+const MSADPCM_ADAPTION: [i32; 16] = [
+    230, 230, 230, 230, 307, 409, 512, 614, 768, 614, 512, 409, 307, 230, 230, 230,
+];
+
+const MSADPCM_COEFF: [[i32; 2]; 7] = [
+    [256, 0],
+    [512, -256],
+    [0, 0],
+    [192, 64],
+    [240, 0],
+    [460, -208],
+    [392, -232],
+];
+
+fn clamp16(v: i32) -> i32 {
+    v.clamp(-32768, 32767)
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ImaState {
+    sample: i32,
+    index: i32,
+}
+
+impl ImaState {
+    fn step(&mut self, nibble: u8) {
+        let n = nibble as usize;
+        self.sample = clamp16(self.sample + IMA_CODEWORD[n] * IMA_STEP_SIZE[self.index as usize] / 8);
+        self.index = (self.index + IMA_INDEX_ADJUST[n]).clamp(0, 88);
+    }
+
+    // the nibble that decodes closest to `target`.
+    fn encode(&mut self, target: i32) -> u8 {
+        let nibble = (0..16u8)
+            .min_by_key(|&n| {
+                let mut next = *self;
+                next.step(n);
+                (next.sample - target).abs()
+            })
+            .unwrap();
+        self.step(nibble);
+        nibble
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct MsState {
+    coeff: [i32; 2],
+    delta: i32,
+    // most recent sample first.
+    samples: [i32; 2],
+}
+
+impl MsState {
+    // i64 keeps malformed deltas from overflowing; valid streams never leave the i16 range.
+    fn step(&mut self, nibble: u8) -> i32 {
+        let delta = self.delta as i64;
+        let pred = (self.samples[0] * self.coeff[0] + self.samples[1] * self.coeff[1]) as i64 / 256;
+        let pred = (pred + ((nibble as i64 ^ 0x08) - 0x08) * delta).clamp(-32768, 32767) as i32;
+        self.samples = [pred, self.samples[0]];
+        self.delta = (MSADPCM_ADAPTION[nibble as usize] as i64 * delta / 256).clamp(16, i32::MAX as i64) as i32;
+        pred
+    }
+
+    // the nibble that decodes closest to `target` without growing the delta past what an i16 header
+    // can hold. the shrinking nibbles always qualify.
+    fn encode(&mut self, target: i32) -> (u8, i32) {
+        let nibble = (0..16u8)
+            .filter_map(|n| {
+                let mut next = *self;
+                let error = (next.step(n) - target).abs();
+                if next.delta > i16::MAX as i32 {
+                    None
+                } else {
+                    Some((n, error))
+                }
+            })
+            .min_by_key(|&(_, error)| error)
+            .unwrap()
+            .0;
+        let error = (self.step(nibble) - target).abs();
+        (nibble, error)
+    }
+}
+
+fn check_samples(format: AdpcmFormat, channels: Channels, block_frames: usize, samples: &[i16]) -> Result<usize> {
+    let block_bytes = format.checked_block_bytes(channels, block_frames)?;
+    ensure(
+        samples.len() % channels.count() == 0,
+        "sample count must be a whole number of frames",
+    )?;
+    Ok(block_bytes)
+}
+
+/// encodes interleaved 16-bit `samples` into blocks of `block_frames` sample frames.
+///
+/// the last block is padded by repeating the final frame, so the result always holds whole blocks.
+pub fn encode(format: AdpcmFormat, channels: Channels, block_frames: usize, samples: &[i16]) -> Result<Vec<u8>> {
+    let block_bytes = check_samples(format, channels, block_frames, samples)?;
+    let nch = channels.count();
+    let block_len = block_frames * nch;
+    let mut out = Vec::with_capacity((samples.len() / block_len + 1) * block_bytes);
+    let mut ima = [ImaState::default(); 2];
+    // start at the step size of the first difference instead of ramping up from the smallest step.
+    if samples.len() >= nch * 2 {
+        for (c, s) in ima[..nch].iter_mut().enumerate() {
+            let diff = (samples[nch + c] as i32 - samples[c] as i32).abs();
+            s.index = IMA_STEP_SIZE.iter().position(|&step| step >= diff).unwrap_or(88) as i32;
+        }
+    }
+    let mut block = vec![0i16; block_len];
+    for chunk in samples.chunks(block_len) {
+        block[..chunk.len()].copy_from_slice(chunk);
+        for i in chunk.len()..block_len {
+            block[i] = block[i - nch];
+        }
+        match format {
+            AdpcmFormat::Ima4 => encode_ima4_block(&mut ima[..nch], &block, &mut out),
+            AdpcmFormat::MsAdpcm => encode_msadpcm_block(nch, &block, &mut out),
+        }
+    }
+    Ok(out)
+}
+
+fn encode_ima4_block(state: &mut [ImaState], block: &[i16], out: &mut Vec<u8>) {
+    let nch = state.len();
+    for (c, s) in state.iter_mut().enumerate() {
+        s.sample = block[c] as i32;
+        out.extend_from_slice(&(s.sample as i16).to_le_bytes());
+        out.push(s.index as u8);
+        out.push(0);
+    }
+    let frames = block.len() / nch;
+    for group in (1..frames).step_by(8) {
+        for (c, s) in state.iter_mut().enumerate() {
+            let mut code = 0u32;
+            for j in 0..8 {
+                let nibble = s.encode(block[(group + j) * nch + c] as i32);
+                code |= (nibble as u32) << (j * 4);
+            }
+            out.extend_from_slice(&code.to_le_bytes());
+        }
+    }
+}
+
+// encodes one channel's block with `predictor`, returning the nibbles, the initial delta and the error.
+fn encode_msadpcm_channel(block: &[i16], nch: usize, c: usize, predictor: usize) -> (Vec<u8>, i32, i64) {
+    let frames = block.len() / nch;
+    let sample = |i: usize| block[i * nch + c] as i32;
+    // size the first step to the first residual; the adaption takes over from there.
+    let delta = if frames > 2 {
+        ((sample(2) - sample(1)).abs() / 4).clamp(16, 32767)
+    } else {
+        16
+    };
+    let mut state = MsState {
+        coeff: MSADPCM_COEFF[predictor],
+        delta,
+        samples: [sample(1), sample(0)],
+    };
+    let mut error = 0i64;
+    let nibbles = (2..frames)
+        .map(|i| {
+            let (nibble, e) = state.encode(sample(i));
+            error += e as i64 * e as i64;
+            nibble
+        })
+        .collect();
+    (nibbles, delta, error)
+}
+
+fn encode_msadpcm_block(nch: usize, block: &[i16], out: &mut Vec<u8>) {
+    let channels: Vec<_> = (0..nch)
+        .map(|c| {
+            (0..MSADPCM_COEFF.len())
+                .map(|p| (p, encode_msadpcm_channel(block, nch, c, p)))
+                .min_by_key(|(_, (_, _, error))| *error)
+                .unwrap()
+        })
+        .collect();
+    for (predictor, _) in &channels {
+        out.push(*predictor as u8);
+    }
+    for (_, (_, delta, _)) in &channels {
+        out.extend_from_slice(&(*delta as i16).to_le_bytes());
+    }
+    for sample in block[nch..nch * 2].iter().chain(&block[..nch]) {
+        out.extend_from_slice(&sample.to_le_bytes());
+    }
+    // nibbles interleave across channels, high nibble first.
+    let frames = block.len() / nch;
+    let mut high = None;
+    for i in 0..frames - 2 {
+        for (_, (nibbles, _, _)) in &channels {
+            match high.take() {
+                None => high = Some(nibbles[i]),
+                Some(h) => out.push(h << 4 | nibbles[i]),
+            }
+        }
+    }
+    if let Some(h) = high {
+        out.push(h << 4);
+    }
+}
+
+/// decodes whole blocks of `block_frames` sample frames into interleaved 16-bit samples.
+pub fn decode(format: AdpcmFormat, channels: Channels, block_frames: usize, data: &[u8]) -> Result<Vec<i16>> {
+    let block_bytes = format.checked_block_bytes(channels, block_frames)?;
+    ensure(
+        data.len() % block_bytes == 0,
+        "ADPCM data length must be a whole number of blocks",
+    )?;
+    let nch = channels.count();
+    let mut out = Vec::with_capacity(data.len() / block_bytes * block_frames * nch);
+    for block in data.chunks(block_bytes) {
+        match format {
+            AdpcmFormat::Ima4 => decode_ima4_block(nch, block_frames, block, &mut out),
+            AdpcmFormat::MsAdpcm => decode_msadpcm_block(nch, block_frames, block, &mut out),
+        }
+    }
+    Ok(out)
+}
+
+fn decode_ima4_block(nch: usize, block_frames: usize, block: &[u8], out: &mut Vec<i16>) {
+    let start = out.len();
+    out.resize(start + block_frames * nch, 0);
+    let dst = &mut out[start..];
+    let mut state = [ImaState::default(); 2];
+    for (c, s) in state[..nch].iter_mut().enumerate() {
+        let header = &block[c * 4..];
+        s.sample = i16::from_le_bytes([header[0], header[1]]) as i32;
+        s.index = (header[2] as i32).min(88);
+        dst[c] = s.sample as i16;
+    }
+    let mut src = block[nch * 4..].chunks(4);
+    for group in (1..block_frames).step_by(8) {
+        for (c, s) in state[..nch].iter_mut().enumerate() {
+            let bytes = src.next().unwrap();
+            let mut code = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            for j in 0..8 {
+                s.step((code & 0xf) as u8);
+                code >>= 4;
+                dst[(group + j) * nch + c] = s.sample as i16;
+            }
+        }
+    }
+}
+
+fn decode_msadpcm_block(nch: usize, block_frames: usize, block: &[u8], out: &mut Vec<i16>) {
+    let read16 = |at: usize| i16::from_le_bytes([block[at], block[at + 1]]) as i32;
+    let mut state: Vec<MsState> = (0..nch)
+        .map(|c| MsState {
+            coeff: MSADPCM_COEFF[(block[c] as usize).min(6)],
+            delta: read16(nch + c * 2),
+            samples: [read16(nch * 3 + c * 2), read16(nch * 5 + c * 2)],
+        })
+        .collect();
+    // the older of the two header samples comes first.
+    out.extend(state.iter().map(|s| s.samples[1] as i16));
+    out.extend(state.iter().map(|s| s.samples[0] as i16));
+    let mut src = block[nch * 7..].iter();
+    let mut low = None;
+    for _ in 2..block_frames {
+        for s in state.iter_mut() {
+            let nibble = match low.take() {
+                None => {
+                    let byte = *src.next().unwrap();
+                    low = Some(byte & 0x0f);
+                    byte >> 4
+                }
+                Some(l) => l,
+            };
+            out.push(s.step(nibble) as i16);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(frames: usize, channels: usize) -> Vec<i16> {
+        (0..frames * channels)
+            .map(|i| {
+                let t = (i / channels) as f64 / 44100.;
+                let f = 440. * (1 + i % channels) as f64;
+                ((t * f * 2. * std::f64::consts::PI).sin() * 12000.) as i16
+            })
+            .collect()
+    }
+
+    fn max_error(a: &[i16], b: &[i16]) -> i32 {
+        a.iter()
+            .zip(b)
+            .map(|(&x, &y)| (x as i32 - y as i32).abs())
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn block_sizes() {
+        for &(format, channels, frames) in &[
+            (AdpcmFormat::Ima4, Channels::Mono, 65),
+            (AdpcmFormat::Ima4, Channels::Stereo, 1017),
+            (AdpcmFormat::MsAdpcm, Channels::Mono, 64),
+            (AdpcmFormat::MsAdpcm, Channels::Stereo, 512),
+        ] {
+            let pcm = sine(frames * 3, channels.count());
+            let data = encode(format, channels, frames, &pcm).unwrap();
+            assert_eq!(data.len(), 3 * format.block_bytes(channels.count(), frames));
+            assert_eq!(decode(format, channels, frames, &data).unwrap().len(), pcm.len());
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for &format in &[AdpcmFormat::Ima4, AdpcmFormat::MsAdpcm] {
+            for &channels in &[Channels::Mono, Channels::Stereo] {
+                let frames = format.default_block_frames();
+                let pcm = sine(frames * 10, channels.count());
+                let data = encode(format, channels, frames, &pcm).unwrap();
+                let decoded = decode(format, channels, frames, &data).unwrap();
+                assert!(max_error(&pcm, &decoded) < 500, "{:?} {:?}", format, channels);
+                // header samples are stored exactly.
+                assert_eq!(decoded[..channels.count()], pcm[..channels.count()]);
+            }
+        }
+    }
+
+    // xorshift32 white noise over the full i16 range.
+    fn noise(len: usize) -> Vec<i16> {
+        let mut x = 0x1234_5678u32;
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 17;
+                x ^= x << 5;
+                x as i16
+            })
+            .collect()
+    }
+
+    fn square(len: usize, channels: usize) -> Vec<i16> {
+        (0..len)
+            .map(|i| {
+                if (i / channels / 20) % 2 == 0 {
+                    i16::MAX
+                } else {
+                    i16::MIN
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn handles_full_scale_input() {
+        for &format in &[AdpcmFormat::Ima4, AdpcmFormat::MsAdpcm] {
+            for &channels in &[Channels::Mono, Channels::Stereo] {
+                let frames = format.default_block_frames();
+                let len = frames * 10 * channels.count();
+                for pcm in &[noise(len), square(len, channels.count())] {
+                    let data = encode(format, channels, frames, pcm).unwrap();
+                    assert_eq!(decode(format, channels, frames, &data).unwrap().len(), pcm.len());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn decodes_malformed_msadpcm() {
+        // predictor 0, delta 0x7fff, both samples 0, then nibbles that keep growing the delta.
+        let mut block = vec![0, 0xff, 0x7f, 0, 0, 0, 0];
+        block.resize(AdpcmFormat::MsAdpcm.block_bytes(1, 64), 0x77);
+        let decoded = decode(AdpcmFormat::MsAdpcm, Channels::Mono, 64, &block).unwrap();
+        assert_eq!(decoded.len(), 64);
+        assert_eq!(decoded[63], i16::MAX);
+    }
+
+    #[test]
+    fn pads_last_block() {
+        let pcm = sine(100, 1);
+        let data = encode(AdpcmFormat::Ima4, Channels::Mono, 65, &pcm).unwrap();
+        let decoded = decode(AdpcmFormat::Ima4, Channels::Mono, 65, &data).unwrap();
+        assert_eq!(decoded.len(), 130);
+    }
+
+    #[test]
+    fn rejects_bad_layout() {
+        assert!(encode(AdpcmFormat::Ima4, Channels::Mono, 64, &[0; 64]).is_err());
+        assert!(encode(AdpcmFormat::MsAdpcm, Channels::Mono, 65, &[0; 65]).is_err());
+        assert!(encode(AdpcmFormat::Ima4, Channels::Stereo, 65, &[0; 3]).is_err());
+        assert!(decode(AdpcmFormat::Ima4, Channels::Mono, 65, &[0; 35]).is_err());
+    }
+}
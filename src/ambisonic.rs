@@ -0,0 +1,125 @@
+use crate::alext::*;
+use crate::buffer::Buffer;
+use crate::error::{ensure, Error, Result};
+use crate::ext::{is_al_extension_present, require_al_extension};
+use crate::format::{Channels, Sample};
+
+/// channel ordering of ambisonic data. AmbiX is `Acn` with `AmbisonicScaling::Sn3d`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmbisonicLayout {
+    FuMa,
+    Acn,
+}
+
+impl AmbisonicLayout {
+    pub(crate) fn to_raw(self) -> ALint {
+        (match self {
+            AmbisonicLayout::FuMa => AL_FUMA_SOFT,
+            AmbisonicLayout::Acn => AL_ACN_SOFT,
+        }) as ALint
+    }
+
+    pub(crate) fn from_raw(v: ALint) -> Result<Self> {
+        Ok(match v as u32 {
+            AL_FUMA_SOFT => AmbisonicLayout::FuMa,
+            AL_ACN_SOFT => AmbisonicLayout::Acn,
+            _ => return Err(Error::UnknownValue(v)),
+        })
+    }
+
+    pub(crate) fn to_alc(self) -> ALCint {
+        (match self {
+            AmbisonicLayout::FuMa => ALC_FUMA_SOFT,
+            AmbisonicLayout::Acn => ALC_ACN_SOFT,
+        }) as ALCint
+    }
+}
+
+/// normalization of ambisonic data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmbisonicScaling {
+    FuMa,
+    Sn3d,
+    N3d,
+}
+
+impl AmbisonicScaling {
+    pub(crate) fn to_raw(self) -> ALint {
+        (match self {
+            AmbisonicScaling::FuMa => AL_FUMA_SOFT,
+            AmbisonicScaling::Sn3d => AL_SN3D_SOFT,
+            AmbisonicScaling::N3d => AL_N3D_SOFT,
+        }) as ALint
+    }
+
+    pub(crate) fn from_raw(v: ALint) -> Result<Self> {
+        Ok(match v as u32 {
+            AL_FUMA_SOFT => AmbisonicScaling::FuMa,
+            AL_SN3D_SOFT => AmbisonicScaling::Sn3d,
+            AL_N3D_SOFT => AmbisonicScaling::N3d,
+            _ => return Err(Error::UnknownValue(v)),
+        })
+    }
+
+    pub(crate) fn to_alc(self) -> ALCint {
+        (match self {
+            AmbisonicScaling::FuMa => ALC_FUMA_SOFT,
+            AmbisonicScaling::Sn3d => ALC_SN3D_SOFT,
+            AmbisonicScaling::N3d => ALC_N3D_SOFT,
+        }) as ALCint
+    }
+}
+
+impl Buffer {
+    /// uploads first-order B-Format data (`AL_EXT_BFORMAT`) in the given layout and scaling
+    /// (`AL_SOFT_bformat_ex`).
+    ///
+    /// `channels` must be `BFormat2D` or `BFormat3D`. FuMa layout and scaling are the
+    /// `AL_EXT_BFORMAT` defaults and work without `AL_SOFT_bformat_ex`.
+    pub fn bformat_data<T: Sample>(
+        &mut self,
+        channels: Channels,
+        layout: AmbisonicLayout,
+        scaling: AmbisonicScaling,
+        data: &[T],
+        frequency: ALsizei,
+    ) -> Result<()> {
+        ensure(
+            channels == Channels::BFormat2D || channels == Channels::BFormat3D,
+            "B-Format data needs BFormat2D or BFormat3D channels",
+        )?;
+        ensure(
+            data.len() % channels.count() == 0,
+            "B-Format data length must be a whole number of frames",
+        )?;
+        if layout != AmbisonicLayout::FuMa || scaling != AmbisonicScaling::FuMa {
+            require_al_extension("AL_SOFT_bformat_ex\0")?;
+        }
+        self.data(channels, data, frequency)?;
+        if is_al_extension_present("AL_SOFT_bformat_ex\0") {
+            self.set_ambisonic_layout(layout)?;
+            self.set_ambisonic_scaling(scaling)?;
+        }
+        Ok(())
+    }
+
+    pub fn ambisonic_layout(&self) -> Result<AmbisonicLayout> {
+        require_al_extension("AL_SOFT_bformat_ex\0")?;
+        AmbisonicLayout::from_raw(self.get_i(AL_AMBISONIC_LAYOUT_SOFT)?)
+    }
+
+    pub fn set_ambisonic_layout(&mut self, layout: AmbisonicLayout) -> Result<()> {
+        require_al_extension("AL_SOFT_bformat_ex\0")?;
+        self.set_i(AL_AMBISONIC_LAYOUT_SOFT, layout.to_raw())
+    }
+
+    pub fn ambisonic_scaling(&self) -> Result<AmbisonicScaling> {
+        require_al_extension("AL_SOFT_bformat_ex\0")?;
+        AmbisonicScaling::from_raw(self.get_i(AL_AMBISONIC_SCALING_SOFT)?)
+    }
+
+    pub fn set_ambisonic_scaling(&mut self, scaling: AmbisonicScaling) -> Result<()> {
+        require_al_extension("AL_SOFT_bformat_ex\0")?;
+        self.set_i(AL_AMBISONIC_SCALING_SOFT, scaling.to_raw())
+    }
+}
@@ -0,0 +1,77 @@
+use crate::alext::*;
+use crate::error::{check_al, ensure, Result};
+use crate::ext::require_al_extension;
+use crate::source::Source;
+use crate::state::DistanceModel;
+
+/// largest gain the mixer applies to a source (`AL_SOFT_gain_clamp_ex`).
+pub fn gain_limit() -> Result<f32> {
+    require_al_extension("AL_SOFT_gain_clamp_ex\0")?;
+    let v = unsafe { alGetFloat(AL_GAIN_LIMIT_SOFT as ALenum) };
+    check_al()?;
+    Ok(v)
+}
+
+/// whether sources use their own distance model instead of the context's (`AL_EXT_source_distance_model`).
+pub fn source_distance_model_enabled() -> Result<bool> {
+    require_al_extension("AL_EXT_source_distance_model\0")?;
+    let v = unsafe { alIsEnabled(AL_SOURCE_DISTANCE_MODEL as ALenum) };
+    check_al()?;
+    Ok(v == AL_TRUE as ALboolean)
+}
+
+pub fn set_source_distance_model_enabled(enabled: bool) -> Result<()> {
+    require_al_extension("AL_EXT_source_distance_model\0")?;
+    unsafe {
+        if enabled {
+            alEnable(AL_SOURCE_DISTANCE_MODEL as ALenum)
+        } else {
+            alDisable(AL_SOURCE_DISTANCE_MODEL as ALenum)
+        }
+    };
+    check_al()
+}
+
+// without `AL_SOFT_gain_clamp_ex` gain bounds above 1 are invalid.
+fn check_gain_bound(gain: f32) -> Result<()> {
+    ensure(
+        gain.is_finite() && gain >= 0.,
+        "gain bound must be finite and non-negative",
+    )?;
+    if gain > 1. {
+        require_al_extension("AL_SOFT_gain_clamp_ex\0")?;
+    }
+    Ok(())
+}
+
+impl Source {
+    pub fn min_gain(&self) -> Result<f32> {
+        self.get_f(AL_MIN_GAIN)
+    }
+
+    pub fn set_min_gain(&mut self, gain: f32) -> Result<()> {
+        check_gain_bound(gain)?;
+        self.set_f(AL_MIN_GAIN, gain)
+    }
+
+    pub fn max_gain(&self) -> Result<f32> {
+        self.get_f(AL_MAX_GAIN)
+    }
+
+    /// a bound above 1 needs `AL_SOFT_gain_clamp_ex`; the applied gain is still limited by `gain_limit`.
+    pub fn set_max_gain(&mut self, gain: f32) -> Result<()> {
+        check_gain_bound(gain)?;
+        self.set_f(AL_MAX_GAIN, gain)
+    }
+
+    pub fn distance_model(&self) -> Result<DistanceModel> {
+        require_al_extension("AL_EXT_source_distance_model\0")?;
+        DistanceModel::from_raw(self.get_i(AL_DISTANCE_MODEL)?)
+    }
+
+    /// only takes effect while `set_source_distance_model_enabled(true)` is in effect on the context.
+    pub fn set_distance_model(&mut self, model: DistanceModel) -> Result<()> {
+        require_al_extension("AL_EXT_source_distance_model\0")?;
+        self.set_i(AL_DISTANCE_MODEL, model.to_raw())
+    }
+}
@@ -0,0 +1,189 @@
+use crate::alext::*;
+use crate::dither::{to_i16, Dither};
+use crate::error::{check_al, ensure, Error, Result};
+use crate::ext::{is_al_extension_present, require_al_extension};
+use crate::format::{buffer_format, Channels, Sample, SampleType};
+use std::mem;
+use std::ops::Range;
+
+/// owned OpenAL buffer object on the current context.
+#[derive(Debug)]
+pub struct Buffer {
+    id: ALuint,
+    // frame count of the last compressed upload, which its size in bytes doesn't give.
+    compressed_frames: Option<usize>,
+}
+
+impl Buffer {
+    pub fn new() -> Result<Self> {
+        let mut id = 0;
+        unsafe { alGenBuffers(1, &mut id) };
+        check_al()?;
+        Ok(Buffer {
+            id,
+            compressed_frames: None,
+        })
+    }
+
+    pub fn id(&self) -> ALuint {
+        self.id
+    }
+
+    /// uploads interleaved `data` to the buffer, in the channel order documented on `Channels`.
+    ///
+    /// `f64` data without a matching format is converted to `f32`, and float data without
+    /// `AL_EXT_FLOAT32` to 16-bit.
+    pub fn data<T: Sample>(&mut self, channels: Channels, data: &[T], frequency: ALsizei) -> Result<()> {
+        self.data_dithered(channels, data, frequency, Dither::None)
+    }
+
+    /// like `data`, applying `dither` if float samples have to be converted to 16-bit.
+    pub fn data_dithered<T: Sample>(
+        &mut self,
+        channels: Channels,
+        data: &[T],
+        frequency: ALsizei,
+        dither: Dither,
+    ) -> Result<()> {
+        ensure(
+            data.len() % channels.count() == 0,
+            "buffer data length must be a whole number of frames",
+        )?;
+        match buffer_format(channels, T::TYPE) {
+            Ok(format) => self.data_raw(format, data, frequency),
+            Err(Error::ExtensionNotPresent("AL_EXT_DOUBLE\0")) | Err(Error::UnsupportedFormat(_, SampleType::F64)) => {
+                let data: Vec<f32> = data.iter().map(|s| s.to_f64() as f32).collect();
+                self.data_dithered(channels, &data, frequency, dither)
+            }
+            Err(Error::ExtensionNotPresent("AL_EXT_FLOAT32\0")) => {
+                let format = buffer_format(channels, SampleType::I16)?;
+                self.data_raw(format, &to_i16(data, dither), frequency)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // uploads `data` as is in `format`.
+    pub(crate) fn data_raw<T: Copy>(&mut self, format: ALenum, data: &[T], frequency: ALsizei) -> Result<()> {
+        self.compressed_frames = None;
+        unsafe {
+            alBufferData(
+                self.id,
+                format,
+                data.as_ptr() as *const ALvoid,
+                mem::size_of_val(data) as ALsizei,
+                frequency,
+            )
+        };
+        check_al()
+    }
+
+    // deletes the buffer now, reporting the failure `Drop` would swallow.
+    pub(crate) fn delete(self) -> Result<()> {
+        unsafe { alDeleteBuffers(1, &self.id) };
+        mem::forget(self);
+        check_al()
+    }
+
+    pub(crate) fn set_i(&mut self, param: u32, v: ALint) -> Result<()> {
+        unsafe { alBufferi(self.id, param as ALenum, v) };
+        check_al()
+    }
+
+    pub(crate) fn get_i(&self, param: u32) -> Result<ALint> {
+        let mut v = 0;
+        unsafe { alGetBufferi(self.id, param as ALenum, &mut v) };
+        check_al()?;
+        Ok(v)
+    }
+
+    pub fn frequency(&self) -> Result<ALint> {
+        self.get_i(AL_FREQUENCY)
+    }
+
+    pub fn bits(&self) -> Result<ALint> {
+        self.get_i(AL_BITS)
+    }
+
+    pub fn channels(&self) -> Result<ALint> {
+        self.get_i(AL_CHANNELS)
+    }
+
+    /// size of the buffer data in bytes.
+    pub fn size(&self) -> Result<ALint> {
+        self.get_i(AL_SIZE)
+    }
+
+    // uploads data whose frame count doesn't follow from its size, such as ADPCM or G.711.
+    pub(crate) fn data_compressed(
+        &mut self,
+        format: ALenum,
+        data: &[u8],
+        frames: usize,
+        frequency: ALsizei,
+    ) -> Result<()> {
+        self.data_raw(format, data, frequency)?;
+        self.compressed_frames = Some(frames);
+        Ok(())
+    }
+
+    /// length of the buffer data in sample frames.
+    ///
+    /// uses `AL_SAMPLE_LENGTH_SOFT` (`AL_SOFT_buffer_samples`) when available. otherwise compressed
+    /// data reports the frame count it was uploaded with, and PCM derives it from the size.
+    pub fn frames(&self) -> Result<usize> {
+        if is_al_extension_present("AL_SOFT_buffer_samples\0") {
+            return Ok(self.get_i(AL_SAMPLE_LENGTH_SOFT)?.max(0) as usize);
+        }
+        if let Some(frames) = self.compressed_frames {
+            return Ok(frames);
+        }
+        let frame_bits = self.bits()? * self.channels()?;
+        if frame_bits == 0 {
+            return Ok(0);
+        }
+        Ok((self.size()? * 8 / frame_bits) as usize)
+    }
+
+    pub fn loop_points(&self) -> Result<Range<usize>> {
+        let mut v = [0; 2];
+        unsafe { alGetBufferiv(self.id, AL_LOOP_POINTS_SOFT as ALenum, v.as_mut_ptr()) };
+        check_al()?;
+        Ok(v[0] as usize..v[1] as usize)
+    }
+
+    /// loops playback over `frames` instead of the whole buffer (`AL_SOFT_loop_points`).
+    ///
+    /// fails with `Error::BufferInUse` while the buffer is attached to a source.
+    pub fn set_loop_points(&mut self, frames: Range<usize>) -> Result<()> {
+        require_al_extension("AL_SOFT_loop_points\0")?;
+        ensure(frames.start < frames.end, "loop start must be before loop end")?;
+        ensure(frames.end <= self.frames()?, "loop end is past the end of the buffer")?;
+        let v = [frames.start as ALint, frames.end as ALint];
+        unsafe { alBufferiv(self.id, AL_LOOP_POINTS_SOFT as ALenum, v.as_ptr()) };
+        check_al().map_err(|err| match err {
+            Error::Al(e) if e == AL_INVALID_OPERATION as ALenum => Error::BufferInUse,
+            err => err,
+        })
+    }
+
+    /// `set_loop_points` with the loop given in seconds, rounded to the nearest frame.
+    pub fn set_loop_points_secs(&mut self, seconds: Range<f64>) -> Result<()> {
+        ensure(
+            seconds.start >= 0. && seconds.end.is_finite(),
+            "loop points must be finite and non-negative",
+        )?;
+        let frequency = self.frequency()? as f64;
+        let start = (seconds.start * frequency).round() as usize;
+        let end = (seconds.end * frequency).round() as usize;
+        self.set_loop_points(start..end)
+    }
+}
+
+impl Drop for Buffer {
+    fn drop(&mut self) {
+        unsafe { alDeleteBuffers(1, &self.id) };
+        // a buffer still in use can not be deleted; don't leave the error behind.
+        let _ = check_al();
+    }
+}
@@ -0,0 +1,107 @@
+use crate::alext::*;
+use crate::buffer::Buffer;
+use crate::error::{check_al, Result};
+use crate::ext::require_al_extension;
+use crate::format::{buffer_format, Channels, Sample};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::{mem, slice};
+
+pub type BufferCallback<T> = Box<dyn FnMut(&mut [T]) -> usize + Send>;
+
+struct CallbackState<T: Sample> {
+    callback: BufferCallback<T>,
+    channels: usize,
+    panicked: Arc<AtomicBool>,
+}
+
+// called from the mixer thread with `numbytes` of room at `sampledata`.
+unsafe extern "C" fn trampoline<T: Sample>(
+    userptr: *mut ALvoid,
+    sampledata: *mut ALvoid,
+    numbytes: ALsizei,
+) -> ALsizei {
+    let state = &mut *(userptr as *mut CallbackState<T>);
+    if state.panicked.load(Ordering::Relaxed) {
+        return 0;
+    }
+    let len = numbytes.max(0) as usize / mem::size_of::<T>();
+    let out = slice::from_raw_parts_mut(sampledata as *mut T, len);
+    // unwinding into C is undefined behavior; a panic ends the stream instead.
+    match panic::catch_unwind(AssertUnwindSafe(|| (state.callback)(out))) {
+        Ok(written) => {
+            let written = written.min(len);
+            ((written - written % state.channels) * mem::size_of::<T>()) as ALsizei
+        }
+        Err(_) => {
+            state.panicked.store(true, Ordering::Relaxed);
+            0
+        }
+    }
+}
+
+/// a buffer whose samples are produced on demand by a closure (`AL_SOFT_callback_buffer`).
+///
+/// the closure fills the slice with interleaved samples and returns how many it wrote; writing
+/// fewer than requested ends playback. it runs on the mixer thread, so keep it short.
+pub struct CallbackBuffer<T: Sample> {
+    buffer: Option<Buffer>,
+    state: *mut CallbackState<T>,
+    panicked: Arc<AtomicBool>,
+}
+
+impl<T: Sample> CallbackBuffer<T> {
+    pub fn new(channels: Channels, frequency: ALsizei, callback: BufferCallback<T>) -> Result<Self> {
+        require_al_extension("AL_SOFT_callback_buffer\0")?;
+        let format = buffer_format(channels, T::TYPE)?;
+        let buffer = Buffer::new()?;
+        let panicked = Arc::new(AtomicBool::new(false));
+        let state = Box::into_raw(Box::new(CallbackState {
+            callback,
+            channels: channels.count(),
+            panicked: panicked.clone(),
+        }));
+        unsafe {
+            alBufferCallbackSOFT(
+                buffer.id(),
+                format,
+                frequency,
+                Some(trampoline::<T>),
+                state as *mut ALvoid,
+            )
+        };
+        if let Err(err) = check_al() {
+            // the buffer never saw the pointer.
+            drop(unsafe { Box::from_raw(state) });
+            return Err(err);
+        }
+        Ok(CallbackBuffer {
+            buffer: Some(buffer),
+            state,
+            panicked,
+        })
+    }
+
+    /// the buffer to attach to a source with `Source::set_buffer`.
+    pub fn buffer(&self) -> &Buffer {
+        self.buffer.as_ref().expect("buffer is only taken on drop")
+    }
+
+    /// `true` if the closure panicked; playback of the buffer ends at that point.
+    pub fn panicked(&self) -> bool {
+        self.panicked.load(Ordering::Relaxed)
+    }
+}
+
+impl<T: Sample> Drop for CallbackBuffer<T> {
+    fn drop(&mut self) {
+        if let Some(buffer) = self.buffer.take() {
+            // while a source still holds the buffer the mixer may call back at any time, so the
+            // closure must outlive it: leak it rather than free it under the mixer.
+            if buffer.delete().is_ok() {
+                drop(unsafe { Box::from_raw(self.state) });
+            }
+        }
+    }
+}
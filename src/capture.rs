@@ -0,0 +1,174 @@
+use crate::alext::*;
+use crate::device::to_cstring;
+use crate::error::{alc_error_or, check_alc, ensure, Error, Result};
+use crate::format::{al_format, Channels, Sample};
+use std::marker::PhantomData;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+/// owned capture device recording interleaved `T` samples.
+#[derive(Debug)]
+pub struct CaptureDevice<T: Sample> {
+    raw: *mut ALCdevice,
+    channels: Channels,
+    frequency: ALCuint,
+    _sample: PhantomData<T>,
+}
+
+// a capture device has no context; the ALC capture functions may be called from any thread.
+unsafe impl<T: Sample> Send for CaptureDevice<T> {}
+
+impl<T: Sample> CaptureDevice<T> {
+    /// opens the capture device named `name`, or the default one with `None`.
+    ///
+    /// `buffer_frames` is the size of the device's ring buffer; older frames are lost when it overflows.
+    pub fn open(name: Option<&str>, channels: Channels, frequency: ALCuint, buffer_frames: usize) -> Result<Self> {
+        let format = al_format(channels, T::TYPE).ok_or(Error::UnsupportedFormat(channels, T::TYPE))?;
+        ensure(buffer_frames > 0, "capture buffer must hold at least one frame")?;
+        let name = name.map(to_cstring).transpose()?;
+        let raw = unsafe {
+            alcCaptureOpenDevice(
+                name.as_ref().map_or(ptr::null(), |n| n.as_ptr()),
+                frequency,
+                format as ALCenum,
+                buffer_frames as ALCsizei,
+            )
+        };
+        if raw.is_null() {
+            return Err(alc_error_or(ptr::null_mut(), ALC_INVALID_VALUE));
+        }
+        Ok(CaptureDevice {
+            raw,
+            channels,
+            frequency,
+            _sample: PhantomData,
+        })
+    }
+
+    pub fn raw(&self) -> *mut ALCdevice {
+        self.raw
+    }
+
+    pub fn channels(&self) -> Channels {
+        self.channels
+    }
+
+    pub fn frequency(&self) -> ALCuint {
+        self.frequency
+    }
+
+    pub fn start(&mut self) -> Result<()> {
+        unsafe { alcCaptureStart(self.raw) };
+        check_alc(self.raw)
+    }
+
+    pub fn stop(&mut self) -> Result<()> {
+        unsafe { alcCaptureStop(self.raw) };
+        check_alc(self.raw)
+    }
+
+    /// number of captured frames ready to be read.
+    pub fn available_frames(&self) -> Result<usize> {
+        let mut v = 0;
+        unsafe { alcGetIntegerv(self.raw, ALC_CAPTURE_SAMPLES as ALCenum, 1, &mut v) };
+        check_alc(self.raw)?;
+        Ok(v as usize)
+    }
+
+    /// reads as many whole frames as are available and fit in `out`, returning the number of frames read.
+    pub fn read(&mut self, out: &mut [T]) -> Result<usize> {
+        let frames = self.available_frames()?.min(out.len() / self.channels.count());
+        if frames > 0 {
+            unsafe { alcCaptureSamples(self.raw, out.as_mut_ptr() as *mut ALCvoid, frames as ALCsizei) };
+            check_alc(self.raw)?;
+        }
+        Ok(frames)
+    }
+
+    fn read_available(&mut self) -> Result<Vec<T>> {
+        let channels = self.channels.count();
+        let mut samples = vec![T::default(); self.available_frames()? * channels];
+        let frames = self.read(&mut samples)?;
+        samples.truncate(frames * channels);
+        Ok(samples)
+    }
+
+    /// starts capturing on a thread that polls every `interval` and sends what it read.
+    pub fn spawn(mut self, interval: Duration) -> Result<CaptureThread<T>> {
+        self.start()?;
+        let (sender, receiver) = mpsc::channel();
+        let running = Arc::new(AtomicBool::new(true));
+        let flag = running.clone();
+        let handle = thread::spawn(move || {
+            while flag.load(Ordering::Acquire) {
+                match self.read_available() {
+                    Ok(samples) => {
+                        if !samples.is_empty() && sender.send(Ok(samples)).is_err() {
+                            break;
+                        }
+                    }
+                    Err(err) => {
+                        let _ = sender.send(Err(err));
+                        break;
+                    }
+                }
+                thread::sleep(interval);
+            }
+            let _ = self.stop();
+            self
+        });
+        Ok(CaptureThread {
+            receiver,
+            running,
+            handle: Some(handle),
+        })
+    }
+}
+
+impl<T: Sample> Drop for CaptureDevice<T> {
+    fn drop(&mut self) {
+        unsafe { alcCaptureCloseDevice(self.raw) };
+    }
+}
+
+/// a capture device polled on its own thread, delivering chunks of interleaved samples.
+#[derive(Debug)]
+pub struct CaptureThread<T: Sample> {
+    receiver: mpsc::Receiver<Result<Vec<T>>>,
+    running: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<CaptureDevice<T>>>,
+}
+
+impl<T: Sample> CaptureThread<T> {
+    pub fn receiver(&self) -> &mpsc::Receiver<Result<Vec<T>>> {
+        &self.receiver
+    }
+
+    /// blocks for each chunk until the thread stops after an error.
+    pub fn iter(&self) -> mpsc::Iter<'_, Result<Vec<T>>> {
+        self.receiver.iter()
+    }
+
+    fn join(&mut self) -> Option<CaptureDevice<T>> {
+        self.running.store(false, Ordering::Release);
+        let handle = self.handle.take()?;
+        match handle.join() {
+            Ok(device) => Some(device),
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+
+    /// stops capturing and returns the device; chunks not yet received are dropped.
+    pub fn stop(mut self) -> CaptureDevice<T> {
+        self.join().expect("capture thread already joined")
+    }
+}
+
+impl<T: Sample> Drop for CaptureThread<T> {
+    fn drop(&mut self) {
+        self.join();
+    }
+}
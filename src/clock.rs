@@ -0,0 +1,91 @@
+use crate::alext::*;
+use crate::device::Device;
+use crate::error::{check_alc, Result};
+use crate::ext::require_alc_extension;
+use std::time::{Duration, Instant};
+
+fn nanos(v: ALCint64SOFT) -> Duration {
+    Duration::from_nanos(v.max(0) as u64)
+}
+
+/// device clock and output latency sampled at the same moment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockLatency {
+    pub clock: Duration,
+    pub latency: Duration,
+}
+
+impl Device {
+    fn get_integers64(&self, param: u32, values: &mut [ALCint64SOFT]) -> Result<()> {
+        require_alc_extension(self.raw(), "ALC_SOFT_device_clock\0")?;
+        unsafe {
+            alcGetInteger64vSOFT(
+                self.raw(),
+                param as ALCenum,
+                values.len() as ALsizei,
+                values.as_mut_ptr(),
+            )
+        };
+        check_alc(self.raw())
+    }
+
+    /// time the device has spent mixing (`ALC_SOFT_device_clock`).
+    pub fn clock(&self) -> Result<Duration> {
+        let mut v = [0];
+        self.get_integers64(ALC_DEVICE_CLOCK_SOFT, &mut v)?;
+        Ok(nanos(v[0]))
+    }
+
+    /// time between mixing a sample and it reaching the output.
+    pub fn latency(&self) -> Result<Duration> {
+        let mut v = [0];
+        self.get_integers64(ALC_DEVICE_LATENCY_SOFT, &mut v)?;
+        Ok(nanos(v[0]))
+    }
+
+    pub fn clock_latency(&self) -> Result<ClockLatency> {
+        let mut v = [0; 2];
+        self.get_integers64(ALC_DEVICE_CLOCK_LATENCY_SOFT, &mut v)?;
+        Ok(ClockLatency {
+            clock: nanos(v[0]),
+            latency: nanos(v[1]),
+        })
+    }
+}
+
+/// maps device clock times to `Instant`s.
+///
+/// the device clock drifts against the system clock, so call `sync` again every few seconds.
+#[derive(Debug, Clone, Copy)]
+pub struct DeviceClock {
+    instant: Instant,
+    sample: ClockLatency,
+}
+
+impl DeviceClock {
+    pub fn sync(device: &Device) -> Result<Self> {
+        let sample = device.clock_latency()?;
+        Ok(DeviceClock {
+            instant: Instant::now(),
+            sample,
+        })
+    }
+
+    pub fn latency(&self) -> Duration {
+        self.sample.latency
+    }
+
+    /// the instant the device clock reads (or read) `device_time`.
+    pub fn to_instant(&self, device_time: Duration) -> Instant {
+        if device_time >= self.sample.clock {
+            self.instant + (device_time - self.sample.clock)
+        } else {
+            self.instant - (self.sample.clock - device_time)
+        }
+    }
+
+    /// the instant audio mixed at `device_time` is heard.
+    pub fn output_instant(&self, device_time: Duration) -> Instant {
+        self.to_instant(device_time + self.sample.latency)
+    }
+}
@@ -0,0 +1,139 @@
+use crate::alext::*;
+use crate::device::Device;
+use crate::error::{alc_error_or, check_al, check_alc, Result};
+use crate::ext::is_al_extension_present;
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::ptr;
+
+/// owned context on a `Device`, which it borrows so the device can't be closed under it.
+#[derive(Debug)]
+pub struct Context<'d> {
+    raw: *mut ALCcontext,
+    device: *mut ALCdevice,
+    pub(crate) subscribed: Cell<bool>,
+    defer_depth: Cell<u32>,
+    _device: PhantomData<&'d Device>,
+}
+
+impl<'d> Context<'d> {
+    /// creates a context with `attrs`, a list of `ALC_*` key/value pairs without the terminating 0.
+    pub fn new(device: &'d Device, attrs: &[ALCint]) -> Result<Self> {
+        unsafe { Context::new_unbound(device, attrs) }
+    }
+
+    // like `new` without borrowing `device`; the caller must drop the context before the device.
+    pub(crate) unsafe fn new_unbound(device: &Device, attrs: &[ALCint]) -> Result<Self> {
+        let mut list = attrs.to_vec();
+        list.push(0);
+        let raw = unsafe { alcCreateContext(device.raw(), list.as_ptr()) };
+        if raw.is_null() {
+            return Err(alc_error_or(device.raw(), ALC_INVALID_VALUE));
+        }
+        Ok(Context {
+            raw,
+            device: device.raw(),
+            subscribed: Cell::new(false),
+            defer_depth: Cell::new(0),
+            _device: PhantomData,
+        })
+    }
+
+    pub fn raw(&self) -> *mut ALCcontext {
+        self.raw
+    }
+
+    pub fn device_raw(&self) -> *mut ALCdevice {
+        self.device
+    }
+
+    pub fn make_current(&self) -> Result<()> {
+        if unsafe { alcMakeContextCurrent(self.raw) } == ALC_FALSE as ALCboolean {
+            return Err(alc_error_or(self.device, ALC_INVALID_CONTEXT));
+        }
+        Ok(())
+    }
+
+    pub fn is_current(&self) -> bool {
+        let current = unsafe { alcGetCurrentContext() };
+        current == self.raw
+    }
+
+    // runs `f` with this context current, restoring the previous one afterwards.
+    pub(crate) fn with_current<R>(&self, f: impl FnOnce() -> R) -> R {
+        let previous = unsafe { alcGetCurrentContext() };
+        if previous == self.raw {
+            return f();
+        }
+        unsafe { alcMakeContextCurrent(self.raw) };
+        let r = f();
+        unsafe { alcMakeContextCurrent(previous) };
+        r
+    }
+
+    /// holds back property changes until the returned guard is dropped, then applies them at once.
+    ///
+    /// uses `AL_SOFT_deferred_updates`, falling back to `alcSuspendContext`. guards may nest; only
+    /// dropping the outermost one applies the changes.
+    pub fn defer_updates(&self) -> Result<DeferGuard<'_>> {
+        let depth = self.defer_depth.get();
+        if depth == 0 {
+            self.with_current(|| {
+                if is_al_extension_present("AL_SOFT_deferred_updates\0") {
+                    unsafe { alDeferUpdatesSOFT() };
+                    check_al()
+                } else {
+                    unsafe { alcSuspendContext(self.raw) };
+                    check_alc(self.device)
+                }
+            })?;
+        }
+        self.defer_depth.set(depth + 1);
+        Ok(DeferGuard { context: self })
+    }
+
+    /// runs `f` with this context current and its updates deferred, see `defer_updates`.
+    pub fn batch<R>(&self, f: impl FnOnce(&Self) -> R) -> Result<R> {
+        let _guard = self.defer_updates()?;
+        Ok(self.with_current(|| f(self)))
+    }
+
+    /// releases whatever context is current.
+    pub fn clear_current() {
+        unsafe { alcMakeContextCurrent(ptr::null_mut()) };
+    }
+}
+
+/// applies deferred updates of its context when the outermost guard is dropped.
+#[derive(Debug)]
+pub struct DeferGuard<'a> {
+    context: &'a Context<'a>,
+}
+
+impl Drop for DeferGuard<'_> {
+    fn drop(&mut self) {
+        let context = self.context;
+        let depth = context.defer_depth.get() - 1;
+        context.defer_depth.set(depth);
+        if depth == 0 {
+            context.with_current(|| {
+                if is_al_extension_present("AL_SOFT_deferred_updates\0") {
+                    unsafe { alProcessUpdatesSOFT() };
+                    let _ = check_al();
+                } else {
+                    unsafe { alcProcessContext(context.raw) };
+                    let _ = check_alc(context.device);
+                }
+            });
+        }
+    }
+}
+
+impl Drop for Context<'_> {
+    fn drop(&mut self) {
+        if self.is_current() {
+            Context::clear_current();
+        }
+        unsafe { alcDestroyContext(self.raw) };
+    }
+}
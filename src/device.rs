@@ -0,0 +1,130 @@
+use crate::alext::*;
+use crate::error::{alc_error_or, check_alc, Error, Result};
+use crate::ext::{is_alc_extension_present, require_alc_extension};
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+pub(crate) fn to_cstring(s: &str) -> Result<CString> {
+    CString::new(s).map_err(|_| Error::InvalidArgument("string contains a nul byte"))
+}
+
+/// owned playback device. contexts borrow it, so it outlives them.
+#[derive(Debug)]
+pub struct Device {
+    raw: *mut ALCdevice,
+}
+
+impl Device {
+    /// opens the device named `name`, or the default device with `None`.
+    pub fn open(name: Option<&str>) -> Result<Self> {
+        let name = name.map(to_cstring).transpose()?;
+        let raw = unsafe { alcOpenDevice(name.as_ref().map_or(ptr::null(), |n| n.as_ptr())) };
+        if raw.is_null() {
+            // the error of a failed open is reported on the null device.
+            return Err(alc_error_or(ptr::null_mut(), ALC_INVALID_DEVICE));
+        }
+        Ok(Device { raw })
+    }
+
+    // `raw` must be a valid device that nothing else closes.
+    pub(crate) fn from_raw(raw: *mut ALCdevice) -> Self {
+        Device { raw }
+    }
+
+    pub fn raw(&self) -> *mut ALCdevice {
+        self.raw
+    }
+
+    pub fn is_extension_present(&self, ext: &str) -> bool {
+        is_alc_extension_present(self.raw, &format!("{}\0", ext))
+    }
+
+    pub(crate) fn get_integers(&self, param: u32, values: &mut [ALCint]) -> Result<()> {
+        unsafe {
+            alcGetIntegerv(
+                self.raw,
+                param as ALCenum,
+                values.len() as ALCsizei,
+                values.as_mut_ptr(),
+            )
+        };
+        check_alc(self.raw)
+    }
+
+    pub(crate) fn get_integer(&self, param: u32) -> Result<ALCint> {
+        let mut v = [0];
+        self.get_integers(param, &mut v)?;
+        Ok(v[0])
+    }
+
+    pub fn name(&self) -> Result<String> {
+        let s = unsafe { alcGetString(self.raw, ALC_DEVICE_SPECIFIER as ALCenum) };
+        check_alc(self.raw)?;
+        Ok(unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned())
+    }
+
+    pub fn frequency(&self) -> Result<ALCint> {
+        self.get_integer(ALC_FREQUENCY)
+    }
+
+    /// stops mixing without touching any context state (`ALC_SOFT_pause_device`).
+    ///
+    /// sources keep their state and position, and carry on from there after `resume`.
+    pub fn pause(&self) -> Result<()> {
+        require_alc_extension(self.raw, "ALC_SOFT_pause_device\0")?;
+        unsafe { alcDevicePauseSOFT(self.raw) };
+        check_alc(self.raw)
+    }
+
+    pub fn resume(&self) -> Result<()> {
+        require_alc_extension(self.raw, "ALC_SOFT_pause_device\0")?;
+        unsafe { alcDeviceResumeSOFT(self.raw) };
+        check_alc(self.raw)
+    }
+
+    /// moves the device to the output `name` (the default output with `None`), reset with `attrs`
+    /// (without the terminating 0) (`ALC_SOFT_reopen_device`).
+    ///
+    /// contexts, sources and buffers stay valid. on failure the device keeps playing on its old output.
+    pub fn reopen(&self, name: Option<&str>, attrs: &[ALCint]) -> Result<()> {
+        require_alc_extension(self.raw, "ALC_SOFT_reopen_device\0")?;
+        let name = name.map(to_cstring).transpose()?;
+        let mut list = attrs.to_vec();
+        list.push(0);
+        let ok = unsafe {
+            alcReopenDeviceSOFT(
+                self.raw,
+                name.as_ref().map_or(ptr::null(), |n| n.as_ptr()),
+                list.as_ptr(),
+            )
+        };
+        if ok == ALC_FALSE as ALCboolean {
+            return Err(alc_error_or(self.raw, ALC_INVALID_DEVICE));
+        }
+        Ok(())
+    }
+
+    /// pauses the device until the returned guard is dropped.
+    pub fn pause_guard(&self) -> Result<PausedGuard<'_>> {
+        self.pause()?;
+        Ok(PausedGuard { device: self })
+    }
+}
+
+/// resumes its device when dropped.
+#[derive(Debug)]
+pub struct PausedGuard<'a> {
+    device: &'a Device,
+}
+
+impl Drop for PausedGuard<'_> {
+    fn drop(&mut self) {
+        let _ = self.device.resume();
+    }
+}
+
+impl Drop for Device {
+    fn drop(&mut self) {
+        unsafe { alcCloseDevice(self.raw) };
+    }
+}
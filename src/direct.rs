@@ -0,0 +1,49 @@
+use crate::alext::*;
+use crate::error::{Error, Result};
+use crate::ext::require_al_extension;
+use crate::source::Source;
+
+/// how a source's buffer channels are sent straight to the output channels, bypassing panning and HRTF.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectChannels {
+    Off,
+    /// buffer channels without a matching output channel are dropped (`AL_SOFT_direct_channels`).
+    DropMismatch,
+    /// buffer channels without a matching output channel are remixed into the others
+    /// (`AL_SOFT_direct_channels_remix`).
+    RemixMismatch,
+}
+
+impl DirectChannels {
+    pub(crate) fn to_raw(self) -> ALint {
+        (match self {
+            DirectChannels::Off => AL_FALSE,
+            DirectChannels::DropMismatch => AL_DROP_UNMATCHED_SOFT,
+            DirectChannels::RemixMismatch => AL_REMIX_UNMATCHED_SOFT,
+        }) as ALint
+    }
+
+    pub(crate) fn from_raw(v: ALint) -> Result<Self> {
+        Ok(match v as u32 {
+            AL_FALSE => DirectChannels::Off,
+            AL_DROP_UNMATCHED_SOFT => DirectChannels::DropMismatch,
+            AL_REMIX_UNMATCHED_SOFT => DirectChannels::RemixMismatch,
+            _ => return Err(Error::UnknownValue(v)),
+        })
+    }
+}
+
+impl Source {
+    pub fn direct_channels(&self) -> Result<DirectChannels> {
+        require_al_extension("AL_SOFT_direct_channels\0")?;
+        DirectChannels::from_raw(self.get_i(AL_DIRECT_CHANNELS_SOFT)?)
+    }
+
+    pub fn set_direct_channels(&mut self, mode: DirectChannels) -> Result<()> {
+        require_al_extension("AL_SOFT_direct_channels\0")?;
+        if mode == DirectChannels::RemixMismatch {
+            require_al_extension("AL_SOFT_direct_channels_remix\0")?;
+        }
+        self.set_i(AL_DIRECT_CHANNELS_SOFT, mode.to_raw())
+    }
+}
@@ -0,0 +1,272 @@
+use crate::alext::*;
+use crate::context::Context;
+use crate::device::Device;
+use crate::error::Result;
+use crate::events::AlEvent;
+use crate::ext::require_alc_extension;
+use crate::listener::{Listener, Orientation};
+use crate::source::{Source, SourceState};
+use crate::state::{self, DistanceModel};
+
+impl Device {
+    /// `false` once the device has been lost, e.g. a USB headset was unplugged (`ALC_EXT_disconnect`).
+    ///
+    /// a disconnected device stays valid but never mixes again; its contexts can still be queried.
+    pub fn is_connected(&self) -> Result<bool> {
+        require_alc_extension(self.raw(), "ALC_EXT_disconnect\0")?;
+        Ok(self.get_integer(ALC_CONNECTED)? != 0)
+    }
+}
+
+// context state that survives recreating the device; object names don't.
+#[derive(Debug, Clone, Copy)]
+struct ContextSnapshot {
+    gain: f32,
+    position: [f32; 3],
+    velocity: [f32; 3],
+    orientation: Orientation,
+    distance_model: DistanceModel,
+    doppler_factor: f32,
+    speed_of_sound: f32,
+}
+
+impl ContextSnapshot {
+    fn capture() -> Result<Self> {
+        let listener = Listener;
+        Ok(ContextSnapshot {
+            gain: listener.gain()?,
+            position: listener.position()?,
+            velocity: listener.velocity()?,
+            orientation: listener.orientation()?,
+            distance_model: state::distance_model()?,
+            doppler_factor: state::doppler_factor()?,
+            speed_of_sound: state::speed_of_sound()?,
+        })
+    }
+
+    fn apply(&self) -> Result<()> {
+        let mut listener = Listener;
+        listener.set_gain(self.gain)?;
+        listener.set_position(self.position)?;
+        listener.set_velocity(self.velocity)?;
+        listener.set_orientation(self.orientation)?;
+        state::set_distance_model(self.distance_model)?;
+        state::set_doppler_factor(self.doppler_factor)?;
+        state::set_speed_of_sound(self.speed_of_sound)
+    }
+}
+
+/// source properties and playback state that can be carried over to a source on a new context.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SourceSnapshot {
+    gain: f32,
+    pitch: f32,
+    position: [f32; 3],
+    velocity: [f32; 3],
+    direction: [f32; 3],
+    cone_inner_angle: f32,
+    cone_outer_angle: f32,
+    cone_outer_gain: f32,
+    reference_distance: f32,
+    rolloff_factor: f32,
+    max_distance: f32,
+    looping: bool,
+    relative: bool,
+    state: SourceState,
+    offset: f32,
+}
+
+impl Source {
+    pub fn snapshot(&self) -> Result<SourceSnapshot> {
+        Ok(SourceSnapshot {
+            gain: self.gain()?,
+            pitch: self.pitch()?,
+            position: self.position()?,
+            velocity: self.velocity()?,
+            direction: self.direction()?,
+            cone_inner_angle: self.cone_inner_angle()?,
+            cone_outer_angle: self.cone_outer_angle()?,
+            cone_outer_gain: self.cone_outer_gain()?,
+            reference_distance: self.reference_distance()?,
+            rolloff_factor: self.rolloff_factor()?,
+            max_distance: self.max_distance()?,
+            looping: self.looping()?,
+            relative: self.relative()?,
+            state: self.state()?,
+            offset: self.get_f(AL_SEC_OFFSET)?,
+        })
+    }
+}
+
+impl SourceSnapshot {
+    /// applies the snapshot to `source`, resuming playback at the saved offset.
+    ///
+    /// attach or queue the source's buffers first; the offset and playback state need them.
+    pub fn restore(&self, source: &mut Source) -> Result<()> {
+        source.set_gain(self.gain)?;
+        source.set_pitch(self.pitch)?;
+        source.set_position(self.position)?;
+        source.set_velocity(self.velocity)?;
+        source.set_direction(self.direction)?;
+        source.set_cone_inner_angle(self.cone_inner_angle)?;
+        source.set_cone_outer_angle(self.cone_outer_angle)?;
+        source.set_cone_outer_gain(self.cone_outer_gain)?;
+        source.set_reference_distance(self.reference_distance)?;
+        source.set_rolloff_factor(self.rolloff_factor)?;
+        source.set_max_distance(self.max_distance)?;
+        source.set_looping(self.looping)?;
+        source.set_relative(self.relative)?;
+        match self.state {
+            SourceState::Playing | SourceState::Paused => {
+                source.set_f(AL_SEC_OFFSET, self.offset)?;
+                source.play()?;
+                if self.state == SourceState::Paused {
+                    source.pause()?;
+                }
+                Ok(())
+            }
+            SourceState::Initial | SourceState::Stopped => Ok(()),
+        }
+    }
+}
+
+/// objects a `DeviceWatcher` rebuilds when it has to recreate its context.
+///
+/// buffer data can't be read back from OpenAL, so `rebuild` re-uploads it from the caller's assets.
+/// the old objects stay in place until the new context is current, so a failed recovery leaves
+/// them untouched.
+pub trait Restore {
+    type Snapshot;
+    type Rebuilt;
+
+    /// runs with the old context current. reads the state to carry over without changing anything.
+    fn save(&self) -> Result<Self::Snapshot>;
+
+    /// runs with the new context current, after the listener and global state are restored. creates
+    /// the new objects and hands them back; on error, drop the ones already created before returning.
+    fn rebuild(&self, snapshot: Self::Snapshot) -> Result<Self::Rebuilt>;
+
+    /// runs with the old context current, once the new one is current. swaps in the new objects and
+    /// drops the old ones while their names still refer to the old context.
+    fn replace(&mut self, rebuilt: Self::Rebuilt);
+}
+
+impl Restore for () {
+    type Snapshot = ();
+    type Rebuilt = ();
+
+    fn save(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn rebuild(&self, _: ()) -> Result<()> {
+        Ok(())
+    }
+
+    fn replace(&mut self, _: ()) {}
+}
+
+/// a device and its current context that can be rebuilt after the device is disconnected.
+///
+/// with `ALC_SOFT_reopen_device`, recovering moves the device to a working output and every object
+/// stays valid. otherwise the device and context are recreated: the listener and the context-wide
+/// state are restored, and `recover_with` rebuilds sources and buffers through `Restore`.
+#[derive(Debug)]
+pub struct DeviceWatcher {
+    // unbound from `device`; it is dropped first and always replaced together with it.
+    context: Context<'static>,
+    device: Device,
+    name: Option<String>,
+    attrs: Vec<ALCint>,
+    disconnected: bool,
+    generation: u32,
+}
+
+impl DeviceWatcher {
+    /// opens `name` (the default device with `None`) and makes a context with `attrs` current.
+    pub fn open(name: Option<&str>, attrs: &[ALCint]) -> Result<Self> {
+        let device = Device::open(name)?;
+        require_alc_extension(device.raw(), "ALC_EXT_disconnect\0")?;
+        let context = unsafe { Context::new_unbound(&device, attrs)? };
+        context.make_current()?;
+        Ok(DeviceWatcher {
+            context,
+            device,
+            name: name.map(str::to_owned),
+            attrs: attrs.to_vec(),
+            disconnected: false,
+            generation: 0,
+        })
+    }
+
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    pub fn context(&self) -> &Context<'_> {
+        &self.context
+    }
+
+    /// number of times the device and context have been recreated, invalidating their objects.
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected
+    }
+
+    /// returns `AlEvent::Disconnected` the first time the device is found disconnected.
+    pub fn poll(&mut self) -> Result<Option<AlEvent>> {
+        if self.disconnected || self.device.is_connected()? {
+            return Ok(None);
+        }
+        self.disconnected = true;
+        let name = self.device.name().unwrap_or_default();
+        Ok(Some(AlEvent::Disconnected {
+            message: format!("device disconnected: {}", name),
+        }))
+    }
+
+    /// `recover_with` for callers that rebuild their objects themselves.
+    pub fn recover(&mut self) -> Result<()> {
+        self.recover_with(&mut ())
+    }
+
+    /// reopens the device, falling back to the default device if the original one is gone.
+    ///
+    /// when the device has to be recreated, `objects` are saved on the old context and rebuilt on a
+    /// new one, which then becomes current. on failure the watcher keeps the old device, context and
+    /// objects.
+    pub fn recover_with<R: Restore>(&mut self, objects: &mut R) -> Result<()> {
+        if self.device.is_extension_present("ALC_SOFT_reopen_device") {
+            if self.device.reopen(self.name.as_deref(), &self.attrs).is_err() {
+                self.device.reopen(None, &self.attrs)?;
+            }
+            self.disconnected = false;
+            return Ok(());
+        }
+        let global = self.context.with_current(ContextSnapshot::capture)?;
+        let device = match self.name.as_deref().map(|name| Device::open(Some(name))) {
+            Some(Ok(device)) => device,
+            _ => Device::open(None)?,
+        };
+        let context = unsafe { Context::new_unbound(&device, &self.attrs)? };
+        let saved = self.context.with_current(|| objects.save())?;
+        let rebuilt = context.with_current(|| {
+            global.apply()?;
+            objects.rebuild(saved)
+        })?;
+        if let Err(e) = context.make_current() {
+            context.with_current(|| drop(rebuilt));
+            return Err(e);
+        }
+        self.context.with_current(|| objects.replace(rebuilt));
+        // the old context goes before the old device.
+        self.context = context;
+        self.device = device;
+        self.disconnected = false;
+        self.generation += 1;
+        Ok(())
+    }
+}
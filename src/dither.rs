@@ -0,0 +1,61 @@
+use crate::format::Sample;
+
+/// noise added when samples are reduced to 16-bit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dither {
+    None,
+    /// triangular noise of ±1 LSB, which decorrelates the rounding error from the signal.
+    Tpdf,
+}
+
+// xorshift32; dither noise doesn't need a better generator.
+struct Noise(u32);
+
+impl Noise {
+    // uniform in [0, 1).
+    fn next(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0 as f64 / 4294967296.
+    }
+}
+
+pub(crate) fn to_i16<T: Sample>(data: &[T], dither: Dither) -> Vec<i16> {
+    let mut noise = Noise(0x9e37_79b9);
+    data.iter()
+        .map(|&s| {
+            let mut v = s.to_f64() * 32768.;
+            if dither == Dither::Tpdf {
+                v += noise.next() - noise.next();
+            }
+            v.round().clamp(-32768., 32767.) as i16
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_and_clips() {
+        let data = [0f32, 0.5, -0.5, 1., -1., 2., -2.];
+        assert_eq!(
+            to_i16(&data, Dither::None),
+            [0, 16384, -16384, 32767, -32768, 32767, -32768]
+        );
+    }
+
+    #[test]
+    fn tpdf_stays_within_one_lsb() {
+        let data: Vec<f64> = (0..1000).map(|i| (i as f64 * 0.01).sin() * 0.8).collect();
+        let exact = to_i16(&data, Dither::None);
+        let dithered = to_i16(&data, Dither::Tpdf);
+        assert_ne!(exact, dithered);
+        assert!(exact
+            .iter()
+            .zip(&dithered)
+            .all(|(&a, &b)| (a as i32 - b as i32).abs() <= 1));
+    }
+}
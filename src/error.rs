@@ -0,0 +1,95 @@
+use crate::alext::*;
+use crate::format::{Channels, SampleType};
+use std::fmt;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// error code returned by `alGetError`.
+    Al(ALenum),
+    /// error code returned by `alcGetError`.
+    Alc(ALCenum),
+    /// the extension is not present on the current context or device.
+    ExtensionNotPresent(&'static str),
+    /// the driver returned a value this crate does not know about.
+    UnknownValue(ALint),
+    /// an argument was rejected before reaching the driver.
+    InvalidArgument(&'static str),
+    /// the buffer is attached to a source and can't be modified.
+    BufferInUse,
+    /// the combination of channels and sample type is not supported.
+    UnsupportedFormat(Channels, SampleType),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Al(err) => write!(f, "AL error: {} (0x{:x})", al_error_name(*err), err),
+            Error::Alc(err) => write!(f, "ALC error: {} (0x{:x})", alc_error_name(*err), err),
+            Error::ExtensionNotPresent(ext) => write!(f, "extension not present: {}", ext.trim_end_matches('\0')),
+            Error::UnknownValue(v) => write!(f, "unknown value returned by the driver: 0x{:x}", v),
+            Error::InvalidArgument(reason) => write!(f, "invalid argument: {}", reason),
+            Error::BufferInUse => write!(f, "buffer is attached to a source"),
+            Error::UnsupportedFormat(channels, ty) => write!(f, "unsupported format: {:?} {:?}", channels, ty),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+fn al_error_name(err: ALenum) -> &'static str {
+    match err as u32 {
+        AL_INVALID_NAME => "AL_INVALID_NAME",
+        AL_INVALID_ENUM => "AL_INVALID_ENUM",
+        AL_INVALID_VALUE => "AL_INVALID_VALUE",
+        AL_INVALID_OPERATION => "AL_INVALID_OPERATION",
+        AL_OUT_OF_MEMORY => "AL_OUT_OF_MEMORY",
+        _ => "unknown",
+    }
+}
+
+fn alc_error_name(err: ALCenum) -> &'static str {
+    match err as u32 {
+        ALC_INVALID_DEVICE => "ALC_INVALID_DEVICE",
+        ALC_INVALID_CONTEXT => "ALC_INVALID_CONTEXT",
+        ALC_INVALID_ENUM => "ALC_INVALID_ENUM",
+        ALC_INVALID_VALUE => "ALC_INVALID_VALUE",
+        ALC_OUT_OF_MEMORY => "ALC_OUT_OF_MEMORY",
+        _ => "unknown",
+    }
+}
+
+pub(crate) fn check_al() -> Result<()> {
+    let err = unsafe { alGetError() };
+    if err == AL_NO_ERROR as ALenum {
+        Ok(())
+    } else {
+        Err(Error::Al(err))
+    }
+}
+
+pub(crate) fn check_alc(device: *mut ALCdevice) -> Result<()> {
+    let err = unsafe { alcGetError(device) };
+    if err == ALC_NO_ERROR as ALCenum {
+        Ok(())
+    } else {
+        Err(Error::Alc(err))
+    }
+}
+
+// the error reported on `device` after a failed call, or `fallback` if the driver did not set one.
+pub(crate) fn alc_error_or(device: *mut ALCdevice, fallback: u32) -> Error {
+    match check_alc(device) {
+        Err(err) => err,
+        Ok(()) => Error::Alc(fallback as ALCenum),
+    }
+}
+
+pub(crate) fn ensure(cond: bool, reason: &'static str) -> Result<()> {
+    if cond {
+        Ok(())
+    } else {
+        Err(Error::InvalidArgument(reason))
+    }
+}
@@ -0,0 +1,192 @@
+use crate::alext::*;
+use crate::context::Context;
+use crate::error::{check_al, ensure, Result};
+use crate::ext::require_al_extension;
+use crate::source::SourceState;
+use std::ops::{BitOr, BitOrAssign};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::{ptr, slice};
+
+/// set of event types to subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EventMask(u8);
+
+impl EventMask {
+    pub const SOURCE_STATE_CHANGED: EventMask = EventMask(1 << 0);
+    pub const BUFFER_COMPLETED: EventMask = EventMask(1 << 1);
+    pub const DISCONNECTED: EventMask = EventMask(1 << 2);
+    pub const PERFORMANCE: EventMask = EventMask(1 << 3);
+    pub const DEPRECATED: EventMask = EventMask(1 << 4);
+    pub const ALL: EventMask = EventMask(0x1f);
+
+    pub fn contains(self, other: EventMask) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    fn types(self) -> Vec<ALenum> {
+        [
+            (EventMask::SOURCE_STATE_CHANGED, AL_EVENT_TYPE_SOURCE_STATE_CHANGED_SOFT),
+            (EventMask::BUFFER_COMPLETED, AL_EVENT_TYPE_BUFFER_COMPLETED_SOFT),
+            (EventMask::DISCONNECTED, AL_EVENT_TYPE_DISCONNECTED_SOFT),
+            (EventMask::PERFORMANCE, AL_EVENT_TYPE_PERFORMANCE_SOFT),
+            (EventMask::DEPRECATED, AL_EVENT_TYPE_DEPRECATED_SOFT),
+        ]
+        .iter()
+        .filter(|&&(mask, _)| self.contains(mask))
+        .map(|&(_, ty)| ty as ALenum)
+        .collect()
+    }
+}
+
+impl BitOr for EventMask {
+    type Output = EventMask;
+
+    fn bitor(self, rhs: EventMask) -> EventMask {
+        EventMask(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for EventMask {
+    fn bitor_assign(&mut self, rhs: EventMask) {
+        self.0 |= rhs.0;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlEvent {
+    SourceStateChanged {
+        source: ALuint,
+        state: SourceState,
+        message: String,
+    },
+    BufferCompleted {
+        source: ALuint,
+        /// number of buffers completed since the last event.
+        count: u32,
+        message: String,
+    },
+    Disconnected {
+        message: String,
+    },
+    Performance {
+        message: String,
+    },
+    Deprecated {
+        message: String,
+    },
+}
+
+impl AlEvent {
+    fn from_raw(ty: ALenum, object: ALuint, param: ALuint, message: String) -> Option<Self> {
+        Some(match ty as u32 {
+            AL_EVENT_TYPE_SOURCE_STATE_CHANGED_SOFT => AlEvent::SourceStateChanged {
+                source: object,
+                state: SourceState::from_raw(param as ALint).ok()?,
+                message,
+            },
+            AL_EVENT_TYPE_BUFFER_COMPLETED_SOFT => AlEvent::BufferCompleted {
+                source: object,
+                count: param,
+                message,
+            },
+            AL_EVENT_TYPE_DISCONNECTED_SOFT => AlEvent::Disconnected { message },
+            AL_EVENT_TYPE_PERFORMANCE_SOFT => AlEvent::Performance { message },
+            AL_EVENT_TYPE_DEPRECATED_SOFT => AlEvent::Deprecated { message },
+            _ => return None,
+        })
+    }
+}
+
+pub type EventHandler = Box<dyn FnMut(AlEvent) + Send>;
+
+// called from openal-soft's event thread.
+unsafe extern "C" fn trampoline(
+    ty: ALenum,
+    object: ALuint,
+    param: ALuint,
+    length: ALsizei,
+    message: *const ALchar,
+    user: *mut ALvoid,
+) {
+    let handler = &mut *(user as *mut EventHandler);
+    let message = if message.is_null() {
+        String::new()
+    } else {
+        let bytes = slice::from_raw_parts(message as *const u8, length.max(0) as usize);
+        String::from_utf8_lossy(bytes).into_owned()
+    };
+    if let Some(event) = AlEvent::from_raw(ty, object, param, message) {
+        // unwinding into C is undefined behavior; drop the panic with the event.
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| handler(event)));
+    }
+}
+
+/// an active event callback on a context; unsubscribes when dropped.
+pub struct EventSubscription<'a> {
+    context: &'a Context<'a>,
+    handler: *mut EventHandler,
+    receiver: Option<mpsc::Receiver<AlEvent>>,
+}
+
+impl EventSubscription<'_> {
+    /// the receiving end when subscribed with `Context::subscribe`.
+    pub fn receiver(&self) -> Option<&mpsc::Receiver<AlEvent>> {
+        self.receiver.as_ref()
+    }
+
+    pub fn try_iter(&self) -> impl Iterator<Item = AlEvent> + '_ {
+        self.receiver.iter().flat_map(|r| r.try_iter())
+    }
+}
+
+impl Drop for EventSubscription<'_> {
+    fn drop(&mut self) {
+        self.context.with_current(|| unsafe {
+            let types = EventMask::ALL.types();
+            alEventControlSOFT(types.len() as ALsizei, types.as_ptr(), AL_FALSE as ALboolean);
+            // once this returns the event thread is not inside the callback and won't enter it again.
+            alEventCallbackSOFT(None, ptr::null_mut());
+            let _ = check_al();
+        });
+        drop(unsafe { Box::from_raw(self.handler) });
+        self.context.subscribed.set(false);
+    }
+}
+
+impl Context<'_> {
+    /// delivers events of `mask` to a channel (`AL_SOFT_events`).
+    pub fn subscribe(&self, mask: EventMask) -> Result<EventSubscription<'_>> {
+        let (sender, receiver) = mpsc::channel();
+        let mut subscription = self.subscribe_with(
+            mask,
+            Box::new(move |event| {
+                let _ = sender.send(event);
+            }),
+        )?;
+        subscription.receiver = Some(receiver);
+        Ok(subscription)
+    }
+
+    /// calls `handler` on openal-soft's event thread for each event of `mask`.
+    pub fn subscribe_with(&self, mask: EventMask, handler: EventHandler) -> Result<EventSubscription<'_>> {
+        ensure(!self.subscribed.get(), "the context already has an event subscription")?;
+        let handler = Box::into_raw(Box::new(handler));
+        let result = self.with_current(|| {
+            require_al_extension("AL_SOFT_events\0")?;
+            unsafe { alEventCallbackSOFT(Some(trampoline), handler as *mut ALvoid) };
+            check_al()?;
+            let types = mask.types();
+            unsafe { alEventControlSOFT(types.len() as ALsizei, types.as_ptr(), AL_TRUE as ALboolean) };
+            check_al()
+        });
+        let subscription = EventSubscription {
+            context: self,
+            handler,
+            receiver: None,
+        };
+        self.subscribed.set(true);
+        // on failure dropping the subscription unregisters and frees the handler.
+        result.map(|()| subscription)
+    }
+}
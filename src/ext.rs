@@ -0,0 +1,32 @@
+use crate::alext::*;
+use crate::error::{Error, Result};
+
+// extension names must be nul-terminated, e.g. `"AL_EXT_FLOAT32\0"`.
+
+pub(crate) fn is_al_extension_present(ext: &str) -> bool {
+    debug_assert!(ext.ends_with('\0'));
+    let p = unsafe { alIsExtensionPresent(ext.as_ptr() as _) };
+    p == (AL_TRUE as ALboolean)
+}
+
+pub(crate) fn require_al_extension(ext: &'static str) -> Result<()> {
+    if is_al_extension_present(ext) {
+        Ok(())
+    } else {
+        Err(Error::ExtensionNotPresent(ext))
+    }
+}
+
+pub(crate) fn is_alc_extension_present(device: *mut ALCdevice, ext: &str) -> bool {
+    debug_assert!(ext.ends_with('\0'));
+    let p = unsafe { alcIsExtensionPresent(device, ext.as_ptr() as _) };
+    p == (ALC_TRUE as ALCboolean)
+}
+
+pub(crate) fn require_alc_extension(device: *mut ALCdevice, ext: &'static str) -> Result<()> {
+    if is_alc_extension_present(device, ext) {
+        Ok(())
+    } else {
+        Err(Error::ExtensionNotPresent(ext))
+    }
+}
@@ -0,0 +1,152 @@
+use crate::alext::*;
+use crate::error::{Error, Result};
+use crate::ext::require_al_extension;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleType {
+    U8,
+    I16,
+    I32,
+    F32,
+    F64,
+}
+
+/// sample types accepted by the safe buffer API.
+pub trait Sample: Copy + Default + Send + 'static {
+    const TYPE: SampleType;
+
+    /// the sample scaled to `[-1, 1]`.
+    fn to_f64(self) -> f64;
+}
+
+impl Sample for u8 {
+    const TYPE: SampleType = SampleType::U8;
+
+    fn to_f64(self) -> f64 {
+        (self as f64 - 128.) / 128.
+    }
+}
+
+impl Sample for i16 {
+    const TYPE: SampleType = SampleType::I16;
+
+    fn to_f64(self) -> f64 {
+        self as f64 / 32768.
+    }
+}
+
+impl Sample for i32 {
+    const TYPE: SampleType = SampleType::I32;
+
+    fn to_f64(self) -> f64 {
+        self as f64 / 2147483648.
+    }
+}
+
+impl Sample for f32 {
+    const TYPE: SampleType = SampleType::F32;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Sample for f64 {
+    const TYPE: SampleType = SampleType::F64;
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+/// channel layouts, with the order interleaved samples are expected in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channels {
+    Mono,
+    /// left, right.
+    Stereo,
+    /// front left, front right, rear left, rear right.
+    Quad,
+    /// front left, front right, front center, LFE, rear left, rear right.
+    Surround51,
+    /// front left, front right, front center, LFE, rear center, side left, side right.
+    Surround61,
+    /// front left, front right, front center, LFE, rear left, rear right, side left, side right.
+    Surround71,
+    /// first-order ambisonics without height: W, X, Y.
+    BFormat2D,
+    /// first-order ambisonics: W, X, Y, Z.
+    BFormat3D,
+}
+
+impl Channels {
+    pub fn count(self) -> usize {
+        match self {
+            Channels::Mono => 1,
+            Channels::Stereo => 2,
+            Channels::Quad => 4,
+            Channels::Surround51 => 6,
+            Channels::Surround61 => 7,
+            Channels::Surround71 => 8,
+            Channels::BFormat2D => 3,
+            Channels::BFormat3D => 4,
+        }
+    }
+}
+
+/// returns the `AL_FORMAT_*` value for `channels` and `ty`, `None` if there is none.
+pub(crate) fn al_format(channels: Channels, ty: SampleType) -> Option<u32> {
+    Some(match (channels, ty) {
+        (Channels::Mono, SampleType::U8) => AL_FORMAT_MONO8,
+        (Channels::Mono, SampleType::I16) => AL_FORMAT_MONO16,
+        (Channels::Mono, SampleType::F32) => AL_FORMAT_MONO_FLOAT32,
+        (Channels::Stereo, SampleType::U8) => AL_FORMAT_STEREO8,
+        (Channels::Stereo, SampleType::I16) => AL_FORMAT_STEREO16,
+        (Channels::Stereo, SampleType::F32) => AL_FORMAT_STEREO_FLOAT32,
+        (Channels::Mono, SampleType::F64) => AL_FORMAT_MONO_DOUBLE_EXT,
+        (Channels::Stereo, SampleType::F64) => AL_FORMAT_STEREO_DOUBLE_EXT,
+        (Channels::Quad, SampleType::U8) => AL_FORMAT_QUAD8,
+        (Channels::Quad, SampleType::I16) => AL_FORMAT_QUAD16,
+        (Channels::Quad, SampleType::F32) => AL_FORMAT_QUAD32,
+        (Channels::Surround51, SampleType::U8) => AL_FORMAT_51CHN8,
+        (Channels::Surround51, SampleType::I16) => AL_FORMAT_51CHN16,
+        (Channels::Surround51, SampleType::F32) => AL_FORMAT_51CHN32,
+        (Channels::Surround61, SampleType::U8) => AL_FORMAT_61CHN8,
+        (Channels::Surround61, SampleType::I16) => AL_FORMAT_61CHN16,
+        (Channels::Surround61, SampleType::F32) => AL_FORMAT_61CHN32,
+        (Channels::Surround71, SampleType::U8) => AL_FORMAT_71CHN8,
+        (Channels::Surround71, SampleType::I16) => AL_FORMAT_71CHN16,
+        (Channels::Surround71, SampleType::F32) => AL_FORMAT_71CHN32,
+        (Channels::BFormat2D, SampleType::U8) => AL_FORMAT_BFORMAT2D_8,
+        (Channels::BFormat2D, SampleType::I16) => AL_FORMAT_BFORMAT2D_16,
+        (Channels::BFormat2D, SampleType::F32) => AL_FORMAT_BFORMAT2D_FLOAT32,
+        (Channels::BFormat3D, SampleType::U8) => AL_FORMAT_BFORMAT3D_8,
+        (Channels::BFormat3D, SampleType::I16) => AL_FORMAT_BFORMAT3D_16,
+        (Channels::BFormat3D, SampleType::F32) => AL_FORMAT_BFORMAT3D_FLOAT32,
+        _ => return None,
+    })
+}
+
+/// like `al_format`, also checking any extension the format requires on the current context.
+pub(crate) fn buffer_format(channels: Channels, ty: SampleType) -> Result<ALenum> {
+    let format = al_format(channels, ty).ok_or(Error::UnsupportedFormat(channels, ty))?;
+    let multichannel = match channels {
+        Channels::Mono | Channels::Stereo => false,
+        Channels::Quad | Channels::Surround51 | Channels::Surround61 | Channels::Surround71 => {
+            require_al_extension("AL_EXT_MCFORMATS\0")?;
+            true
+        }
+        Channels::BFormat2D | Channels::BFormat3D => {
+            require_al_extension("AL_EXT_BFORMAT\0")?;
+            false
+        }
+    };
+    // the 32-bit multichannel formats are float and part of `AL_EXT_MCFORMATS` itself.
+    if ty == SampleType::F32 && !multichannel {
+        require_al_extension("AL_EXT_FLOAT32\0")?;
+    }
+    if ty == SampleType::F64 {
+        require_al_extension("AL_EXT_DOUBLE\0")?;
+    }
+    Ok(format as ALenum)
+}
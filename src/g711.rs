@@ -0,0 +1,143 @@
+use crate::alext::*;
+use crate::buffer::Buffer;
+use crate::error::{ensure, Error, Result};
+use crate::ext::require_al_extension;
+use crate::format::Channels;
+
+const MULAW_BIAS: i32 = 0x84;
+const MULAW_CLIP: i32 = 32635;
+
+/// 8-bit G.711 companded encodings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum G711Format {
+    /// mu-law (`AL_EXT_MULAW`, `AL_EXT_MULAW_MCFORMATS` for more than 2 channels).
+    MuLaw,
+    /// A-law (`AL_EXT_ALAW`), mono or stereo only.
+    ALaw,
+}
+
+impl G711Format {
+    pub fn encode(self, samples: &[i16]) -> Vec<u8> {
+        let f = match self {
+            G711Format::MuLaw => mulaw_encode,
+            G711Format::ALaw => alaw_encode,
+        };
+        samples.iter().map(|&s| f(s)).collect()
+    }
+
+    pub fn decode(self, data: &[u8]) -> Vec<i16> {
+        let f = match self {
+            G711Format::MuLaw => mulaw_decode,
+            G711Format::ALaw => alaw_decode,
+        };
+        data.iter().map(|&b| f(b)).collect()
+    }
+
+    fn extension(self, channels: Channels) -> &'static str {
+        match (self, channels) {
+            (G711Format::MuLaw, Channels::Mono) | (G711Format::MuLaw, Channels::Stereo) => "AL_EXT_MULAW\0",
+            (G711Format::MuLaw, _) => "AL_EXT_MULAW_MCFORMATS\0",
+            (G711Format::ALaw, _) => "AL_EXT_ALAW\0",
+        }
+    }
+
+    fn al_format(self, channels: Channels) -> Option<ALenum> {
+        Some(match (self, channels) {
+            (G711Format::MuLaw, Channels::Mono) => AL_FORMAT_MONO_MULAW,
+            (G711Format::MuLaw, Channels::Stereo) => AL_FORMAT_STEREO_MULAW,
+            (G711Format::MuLaw, Channels::Quad) => AL_FORMAT_QUAD_MULAW,
+            (G711Format::MuLaw, Channels::Surround51) => AL_FORMAT_51CHN_MULAW,
+            (G711Format::MuLaw, Channels::Surround61) => AL_FORMAT_61CHN_MULAW,
+            (G711Format::MuLaw, Channels::Surround71) => AL_FORMAT_71CHN_MULAW,
+            (G711Format::ALaw, Channels::Mono) => AL_FORMAT_MONO_ALAW_EXT,
+            (G711Format::ALaw, Channels::Stereo) => AL_FORMAT_STEREO_ALAW_EXT,
+            _ => return None,
+        } as ALenum)
+    }
+}
+
+pub fn mulaw_encode(sample: i16) -> u8 {
+    let sample = sample as i32;
+    let sign = if sample < 0 { 0x80 } else { 0 };
+    let magnitude = sample.abs().min(MULAW_CLIP) + MULAW_BIAS;
+    // the segment is the highest set bit above the 7 low bits.
+    let exponent = 7 - ((magnitude >> 7) as u8).leading_zeros() as i32;
+    let mantissa = (magnitude >> (exponent + 3)) & 0x0f;
+    !(sign | exponent << 4 | mantissa) as u8
+}
+
+pub fn mulaw_decode(byte: u8) -> i16 {
+    let byte = !byte as i32;
+    let exponent = (byte >> 4) & 0x07;
+    let magnitude = (((byte & 0x0f) << 3) + MULAW_BIAS) << exponent;
+    let sample = magnitude - MULAW_BIAS;
+    (if byte & 0x80 != 0 { -sample } else { sample }) as i16
+}
+
+pub fn alaw_encode(sample: i16) -> u8 {
+    let sample = sample as i32 >> 3;
+    let (mask, magnitude) = if sample >= 0 {
+        (0xd5, sample)
+    } else {
+        (0x55, -sample - 1)
+    };
+    let segment = 32 - (magnitude as u32 >> 5).leading_zeros() as i32;
+    let mantissa = (magnitude >> segment.max(1)) & 0x0f;
+    ((segment << 4 | mantissa) ^ mask) as u8
+}
+
+pub fn alaw_decode(byte: u8) -> i16 {
+    let byte = (byte ^ 0x55) as i32;
+    let segment = (byte & 0x70) >> 4;
+    let mantissa = (byte & 0x0f) << 4;
+    let magnitude = match segment {
+        0 => mantissa + 8,
+        _ => (mantissa + 0x108) << (segment - 1),
+    };
+    (if byte & 0x80 != 0 { magnitude } else { -magnitude }) as i16
+}
+
+impl Buffer {
+    /// uploads interleaved G.711 samples, in the channel order documented on `Channels`.
+    pub fn g711_data(&mut self, format: G711Format, channels: Channels, data: &[u8], frequency: ALsizei) -> Result<()> {
+        let al_format = format
+            .al_format(channels)
+            .ok_or(Error::InvalidArgument("no G.711 format for these channels"))?;
+        require_al_extension(format.extension(channels))?;
+        ensure(
+            data.len() % channels.count() == 0,
+            "buffer data length must be a whole number of frames",
+        )?;
+        self.data_compressed(al_format, data, data.len() / channels.count(), frequency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_values() {
+        assert_eq!(mulaw_encode(0), 0xff);
+        assert_eq!(mulaw_encode(-1), 0x7f);
+        assert_eq!(mulaw_encode(i16::MAX), 0x80);
+        assert_eq!(mulaw_encode(i16::MIN), 0x00);
+        assert_eq!(mulaw_decode(0x80), 32124);
+        assert_eq!(alaw_encode(0), 0xd5);
+        assert_eq!(alaw_encode(-1), 0x55);
+        assert_eq!(alaw_encode(i16::MAX), 0xaa);
+        assert_eq!(alaw_encode(i16::MIN), 0x2a);
+        assert_eq!(alaw_decode(0xaa), 32256);
+    }
+
+    #[test]
+    fn decode_encode_is_identity() {
+        for byte in 0..=255u8 {
+            // mu-law has both a positive and a negative zero.
+            if byte != 0x7f {
+                assert_eq!(mulaw_encode(mulaw_decode(byte)), byte);
+            }
+            assert_eq!(alaw_encode(alaw_decode(byte)), byte);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::alext::*;
+use crate::device::Device;
+use crate::error::{alc_error_or, check_alc, ensure, Error, Result};
+use crate::ext::require_alc_extension;
+use std::ffi::CStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HrtfStatus {
+    Disabled,
+    Enabled,
+    /// HRTF was requested but the device's configuration denies it.
+    Denied,
+    /// HRTF is forced on by the device's configuration.
+    Required,
+    /// enabled automatically because the output is headphones.
+    HeadphonesDetected,
+    /// the output format is not compatible with HRTF.
+    UnsupportedFormat,
+}
+
+impl HrtfStatus {
+    fn from_raw(v: ALCint) -> Result<Self> {
+        Ok(match v as u32 {
+            ALC_HRTF_DISABLED_SOFT => HrtfStatus::Disabled,
+            ALC_HRTF_ENABLED_SOFT => HrtfStatus::Enabled,
+            ALC_HRTF_DENIED_SOFT => HrtfStatus::Denied,
+            ALC_HRTF_REQUIRED_SOFT => HrtfStatus::Required,
+            ALC_HRTF_HEADPHONES_DETECTED_SOFT => HrtfStatus::HeadphonesDetected,
+            ALC_HRTF_UNSUPPORTED_FORMAT_SOFT => HrtfStatus::UnsupportedFormat,
+            _ => return Err(Error::UnknownValue(v)),
+        })
+    }
+}
+
+/// an HRTF dataset available on a device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hrtf {
+    pub index: usize,
+    pub name: String,
+}
+
+impl Device {
+    fn require_hrtf(&self) -> Result<()> {
+        require_alc_extension(self.raw(), "ALC_SOFT_HRTF\0")
+    }
+
+    pub fn hrtfs(&self) -> Result<Vec<Hrtf>> {
+        self.require_hrtf()?;
+        let count = self.get_integer(ALC_NUM_HRTF_SPECIFIERS_SOFT)?;
+        (0..count)
+            .map(|i| {
+                let name = unsafe { alcGetStringiSOFT(self.raw(), ALC_HRTF_SPECIFIER_SOFT as ALCenum, i) };
+                check_alc(self.raw())?;
+                Ok(Hrtf {
+                    index: i as usize,
+                    name: unsafe { CStr::from_ptr(name) }.to_string_lossy().into_owned(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn hrtf_status(&self) -> Result<HrtfStatus> {
+        self.require_hrtf()?;
+        HrtfStatus::from_raw(self.get_integer(ALC_HRTF_STATUS_SOFT)?)
+    }
+
+    /// name of the HRTF in use, `None` while HRTF is off.
+    pub fn active_hrtf(&self) -> Result<Option<String>> {
+        self.require_hrtf()?;
+        if self.get_integer(ALC_HRTF_SOFT)? == ALC_FALSE as ALCint {
+            return Ok(None);
+        }
+        let name = unsafe { alcGetString(self.raw(), ALC_HRTF_SPECIFIER_SOFT as ALCenum) };
+        check_alc(self.raw())?;
+        Ok(Some(unsafe { CStr::from_ptr(name) }.to_string_lossy().into_owned()))
+    }
+
+    /// resets the device with `attrs` (without the terminating 0), keeping its contexts and objects.
+    pub fn reset(&self, attrs: &[ALCint]) -> Result<()> {
+        self.require_hrtf()?;
+        let mut list = attrs.to_vec();
+        list.push(0);
+        if unsafe { alcResetDeviceSOFT(self.raw(), list.as_ptr()) } == ALC_FALSE as ALCboolean {
+            return Err(alc_error_or(self.raw(), ALC_INVALID_DEVICE));
+        }
+        Ok(())
+    }
+
+    /// switches to the HRTF at `index` of `hrtfs`, returning the resulting status.
+    pub fn select_hrtf(&self, index: usize) -> Result<HrtfStatus> {
+        self.require_hrtf()?;
+        let count = self.get_integer(ALC_NUM_HRTF_SPECIFIERS_SOFT)? as usize;
+        ensure(index < count, "HRTF index out of range")?;
+        self.reset(&[
+            ALC_HRTF_SOFT as ALCint,
+            ALC_TRUE as ALCint,
+            ALC_HRTF_ID_SOFT as ALCint,
+            index as ALCint,
+        ])?;
+        self.hrtf_status()
+    }
+
+    pub fn select_hrtf_by_name(&self, name: &str) -> Result<HrtfStatus> {
+        let hrtf = self
+            .hrtfs()?
+            .into_iter()
+            .find(|h| h.name == name)
+            .ok_or(Error::InvalidArgument("no HRTF with that name"))?;
+        self.select_hrtf(hrtf.index)
+    }
+
+    /// lets the device decide whether to use HRTF, e.g. enabling it for headphones.
+    pub fn auto_hrtf(&self) -> Result<HrtfStatus> {
+        self.reset(&[ALC_HRTF_SOFT as ALCint, ALC_DONT_CARE_SOFT as ALCint])?;
+        self.hrtf_status()
+    }
+
+    pub fn disable_hrtf(&self) -> Result<HrtfStatus> {
+        self.reset(&[ALC_HRTF_SOFT as ALCint, ALC_FALSE as ALCint])?;
+        self.hrtf_status()
+    }
+}
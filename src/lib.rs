@@ -1,3 +1,6 @@
+// `is_multiple_of` needs Rust 1.87; the crate supports older toolchains (see `rust-version`).
+#![allow(unknown_lints, clippy::manual_is_multiple_of)]
+
 #[allow(non_upper_case_globals)]
 #[allow(non_camel_case_types)]
 #[allow(non_snake_case)]
@@ -20,6 +23,59 @@ pub mod alext {
     pub mod efx_presets;
 }
 
+pub mod adpcm;
+pub mod ambisonic;
+pub mod attenuation;
+pub mod buffer;
+pub mod callback;
+pub mod capture;
+pub mod clock;
+pub mod context;
+pub mod device;
+pub mod direct;
+pub mod disconnect;
+pub mod dither;
+pub mod error;
+pub mod events;
+mod ext;
+pub mod format;
+pub mod g711;
+pub mod hrtf;
+pub mod listener;
+pub mod loopback;
+pub mod position;
+pub mod radius;
+pub mod resampler;
+pub mod source;
+pub mod spatialize;
+pub mod state;
+pub mod stereo;
+pub mod streaming;
+
+pub use adpcm::AdpcmFormat;
+pub use ambisonic::{AmbisonicLayout, AmbisonicScaling};
+pub use buffer::Buffer;
+pub use callback::CallbackBuffer;
+pub use capture::{CaptureDevice, CaptureThread};
+pub use clock::{ClockLatency, DeviceClock};
+pub use context::{Context, DeferGuard};
+pub use device::{Device, PausedGuard};
+pub use direct::DirectChannels;
+pub use disconnect::{DeviceWatcher, Restore, SourceSnapshot};
+pub use dither::Dither;
+pub use error::{Error, Result};
+pub use events::{AlEvent, EventMask, EventSubscription};
+pub use g711::G711Format;
+pub use hrtf::{Hrtf, HrtfStatus};
+pub use listener::{Listener, Orientation};
+pub use loopback::{AmbisonicFormat, LoopbackDevice};
+pub use position::{PlaybackPosition, PlaybackTime};
+pub use resampler::Resampler;
+pub use source::{Source, SourceState};
+pub use spatialize::Spatialize;
+pub use state::DistanceModel;
+pub use streaming::StreamingSource;
+
 #[cfg(test)]
 mod tests {
     #[test]
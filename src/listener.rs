@@ -0,0 +1,112 @@
+use crate::alext::*;
+use crate::error::{check_al, ensure, Result};
+
+/// `AL_ORIENTATION` as a pair of "at" and "up" vectors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Orientation {
+    pub at: [f32; 3],
+    pub up: [f32; 3],
+}
+
+impl Orientation {
+    fn to_array(self) -> [f32; 6] {
+        let [ax, ay, az] = self.at;
+        let [ux, uy, uz] = self.up;
+        [ax, ay, az, ux, uy, uz]
+    }
+
+    fn from_array(v: [f32; 6]) -> Self {
+        Orientation {
+            at: [v[0], v[1], v[2]],
+            up: [v[3], v[4], v[5]],
+        }
+    }
+}
+
+impl Default for Orientation {
+    fn default() -> Self {
+        Orientation {
+            at: [0., 0., -1.],
+            up: [0., 1., 0.],
+        }
+    }
+}
+
+fn is_finite(v: [f32; 3]) -> bool {
+    v.iter().all(|f| f.is_finite())
+}
+
+fn is_zero(v: [f32; 3]) -> bool {
+    v.iter().all(|&f| f == 0.)
+}
+
+/// the listener of the current context.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Listener;
+
+impl Listener {
+    fn set_3f(&mut self, param: u32, v: [f32; 3]) -> Result<()> {
+        unsafe { alListener3f(param as ALenum, v[0], v[1], v[2]) };
+        check_al()
+    }
+
+    fn get_3f(&self, param: u32) -> Result<[f32; 3]> {
+        let mut v = [0.; 3];
+        unsafe { alGetListenerfv(param as ALenum, v.as_mut_ptr()) };
+        check_al()?;
+        Ok(v)
+    }
+
+    pub fn gain(&self) -> Result<f32> {
+        let mut v = 0.;
+        unsafe { alGetListenerf(AL_GAIN as ALenum, &mut v) };
+        check_al()?;
+        Ok(v)
+    }
+
+    pub fn set_gain(&mut self, gain: f32) -> Result<()> {
+        ensure(
+            gain.is_finite() && gain >= 0.,
+            "listener gain must be finite and non-negative",
+        )?;
+        unsafe { alListenerf(AL_GAIN as ALenum, gain) };
+        check_al()
+    }
+
+    pub fn position(&self) -> Result<[f32; 3]> {
+        self.get_3f(AL_POSITION)
+    }
+
+    pub fn set_position(&mut self, position: [f32; 3]) -> Result<()> {
+        ensure(is_finite(position), "listener position must be finite")?;
+        self.set_3f(AL_POSITION, position)
+    }
+
+    pub fn velocity(&self) -> Result<[f32; 3]> {
+        self.get_3f(AL_VELOCITY)
+    }
+
+    pub fn set_velocity(&mut self, velocity: [f32; 3]) -> Result<()> {
+        ensure(is_finite(velocity), "listener velocity must be finite")?;
+        self.set_3f(AL_VELOCITY, velocity)
+    }
+
+    pub fn orientation(&self) -> Result<Orientation> {
+        let mut v = [0.; 6];
+        unsafe { alGetListenerfv(AL_ORIENTATION as ALenum, v.as_mut_ptr()) };
+        check_al()?;
+        Ok(Orientation::from_array(v))
+    }
+
+    pub fn set_orientation(&mut self, orientation: Orientation) -> Result<()> {
+        let Orientation { at, up } = orientation;
+        ensure(is_finite(at) && is_finite(up), "listener orientation must be finite")?;
+        ensure(
+            !is_zero(at) && !is_zero(up),
+            "listener orientation vectors must be non-zero",
+        )?;
+        let v = orientation.to_array();
+        unsafe { alListenerfv(AL_ORIENTATION as ALenum, v.as_ptr()) };
+        check_al()
+    }
+}
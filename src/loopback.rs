@@ -0,0 +1,188 @@
+use crate::alext::*;
+use crate::ambisonic::{AmbisonicLayout, AmbisonicScaling};
+use crate::context::Context;
+use crate::device::Device;
+use crate::error::{alc_error_or, check_alc, ensure, Error, Result};
+use crate::ext::require_alc_extension;
+use crate::format::{Channels, Sample, SampleType};
+use std::marker::PhantomData;
+use std::ptr;
+
+fn alc_channels(channels: Channels) -> Option<ALCenum> {
+    Some(match channels {
+        Channels::Mono => ALC_MONO_SOFT,
+        Channels::Stereo => ALC_STEREO_SOFT,
+        Channels::Quad => ALC_QUAD_SOFT,
+        Channels::Surround51 => ALC_5POINT1_SOFT,
+        Channels::Surround61 => ALC_6POINT1_SOFT,
+        Channels::Surround71 => ALC_7POINT1_SOFT,
+        Channels::BFormat3D => ALC_BFORMAT3D_SOFT,
+        Channels::BFormat2D => return None,
+    } as ALCenum)
+}
+
+fn alc_type(ty: SampleType) -> Option<ALCenum> {
+    Some(match ty {
+        SampleType::U8 => ALC_UNSIGNED_BYTE_SOFT,
+        SampleType::I16 => ALC_SHORT_SOFT,
+        SampleType::I32 => ALC_INT_SOFT,
+        SampleType::F32 => ALC_FLOAT_SOFT,
+        SampleType::F64 => return None,
+    } as ALCenum)
+}
+
+/// ambisonic output format of a loopback device (`ALC_SOFT_loopback_bformat`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AmbisonicFormat {
+    pub layout: AmbisonicLayout,
+    pub scaling: AmbisonicScaling,
+    pub order: u32,
+}
+
+impl AmbisonicFormat {
+    /// number of ambisonic channels, `(order + 1)²`.
+    pub fn channel_count(&self) -> usize {
+        let n = self.order as usize + 1;
+        n * n
+    }
+}
+
+/// a device that renders into caller-provided slices instead of an audio output (`ALC_SOFT_loopback`).
+#[derive(Debug)]
+pub struct LoopbackDevice<T: Sample> {
+    device: Device,
+    channels: Channels,
+    ambisonic: Option<AmbisonicFormat>,
+    frequency: ALCsizei,
+    _sample: PhantomData<T>,
+}
+
+impl<T: Sample> LoopbackDevice<T> {
+    /// fails with `Error::UnsupportedFormat` if the device can't render `channels` of `T` at `frequency`.
+    ///
+    /// B-Format output goes through `open_ambisonic`.
+    pub fn open(channels: Channels, frequency: ALCsizei) -> Result<Self> {
+        if channels == Channels::BFormat3D {
+            return Err(Error::UnsupportedFormat(channels, T::TYPE));
+        }
+        Self::open_format(channels, None, frequency)
+    }
+
+    /// renders ambisonics of `format.order`, at most `max_ambisonic_order` and 3 with FuMa layout or scaling.
+    pub fn open_ambisonic(format: AmbisonicFormat, frequency: ALCsizei) -> Result<Self> {
+        ensure(format.order >= 1, "ambisonic order must be at least 1")?;
+        if format.layout == AmbisonicLayout::FuMa || format.scaling == AmbisonicScaling::FuMa {
+            ensure(format.order <= 3, "FuMa ambisonics go up to third order")?;
+        }
+        require_alc_extension(ptr::null_mut(), "ALC_SOFT_loopback_bformat\0")?;
+        let loopback = Self::open_format(Channels::BFormat3D, Some(format), frequency)?;
+        ensure(
+            format.order <= loopback.max_ambisonic_order()?,
+            "ambisonic order is above the device's maximum",
+        )?;
+        Ok(loopback)
+    }
+
+    fn open_format(channels: Channels, ambisonic: Option<AmbisonicFormat>, frequency: ALCsizei) -> Result<Self> {
+        let alc_channels = alc_channels(channels).ok_or(Error::UnsupportedFormat(channels, T::TYPE))?;
+        let alc_type = alc_type(T::TYPE).ok_or(Error::UnsupportedFormat(channels, T::TYPE))?;
+        require_alc_extension(ptr::null_mut(), "ALC_SOFT_loopback\0")?;
+        let raw = unsafe { alcLoopbackOpenDeviceSOFT(ptr::null()) };
+        if raw.is_null() {
+            return Err(alc_error_or(ptr::null_mut(), ALC_INVALID_DEVICE));
+        }
+        let device = Device::from_raw(raw);
+
+        let supported = unsafe { alcIsRenderFormatSupportedSOFT(raw, frequency, alc_channels, alc_type) };
+        check_alc(raw)?;
+        if supported == ALC_FALSE as ALCboolean {
+            return Err(Error::UnsupportedFormat(channels, T::TYPE));
+        }
+
+        Ok(LoopbackDevice {
+            device,
+            channels,
+            ambisonic,
+            frequency,
+            _sample: PhantomData,
+        })
+    }
+
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    pub fn channels(&self) -> Channels {
+        self.channels
+    }
+
+    pub fn frequency(&self) -> ALCsizei {
+        self.frequency
+    }
+
+    pub fn ambisonic(&self) -> Option<AmbisonicFormat> {
+        self.ambisonic
+    }
+
+    /// highest ambisonic order the device can render (`ALC_SOFT_loopback_bformat`).
+    pub fn max_ambisonic_order(&self) -> Result<u32> {
+        require_alc_extension(self.device.raw(), "ALC_SOFT_loopback_bformat\0")?;
+        Ok(self.device.get_integer(ALC_MAX_AMBISONIC_ORDER_SOFT)?.max(0) as u32)
+    }
+
+    // interleaved samples per frame.
+    fn frame_channels(&self) -> usize {
+        self.ambisonic.map_or(self.channels.count(), |a| a.channel_count())
+    }
+
+    /// creates a context rendering in the device's format; `attrs` are appended after the format attributes.
+    pub fn create_context(&self, attrs: &[ALCint]) -> Result<Context<'_>> {
+        let mut list = vec![
+            ALC_FORMAT_CHANNELS_SOFT as ALCint,
+            alc_channels(self.channels).expect("checked on open"),
+            ALC_FORMAT_TYPE_SOFT as ALCint,
+            alc_type(T::TYPE).expect("checked on open"),
+            ALC_FREQUENCY as ALCint,
+            self.frequency,
+        ];
+        if let Some(ambisonic) = self.ambisonic {
+            list.extend_from_slice(&[
+                ALC_AMBISONIC_LAYOUT_SOFT as ALCint,
+                ambisonic.layout.to_alc(),
+                ALC_AMBISONIC_SCALING_SOFT as ALCint,
+                ambisonic.scaling.to_alc(),
+                ALC_AMBISONIC_ORDER_SOFT as ALCint,
+                ambisonic.order as ALCint,
+            ]);
+        }
+        list.extend_from_slice(attrs);
+        Context::new(&self.device, &list)
+    }
+
+    /// renders `out.len() / channels` frames of interleaved samples, in ACN or FuMa channel order for
+    /// ambisonic output.
+    ///
+    /// takes `&self`, so it can be called while a context created from this device is alive:
+    ///
+    /// ```no_run
+    /// # use openal_soft_sys::{format::Channels, LoopbackDevice};
+    /// # fn main() -> openal_soft_sys::Result<()> {
+    /// let loopback = LoopbackDevice::<f32>::open(Channels::Stereo, 48000)?;
+    /// let context = loopback.create_context(&[])?;
+    /// context.make_current()?;
+    /// let mut out = vec![0.0; 2 * 1024];
+    /// loopback.render(&mut out)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn render(&self, out: &mut [T]) -> Result<()> {
+        let channels = self.frame_channels();
+        ensure(
+            out.len() % channels == 0,
+            "render buffer length must be a whole number of frames",
+        )?;
+        let frames = out.len() / channels;
+        unsafe { alcRenderSamplesSOFT(self.device.raw(), out.as_mut_ptr() as *mut ALCvoid, frames as ALCsizei) };
+        check_alc(self.device.raw())
+    }
+}
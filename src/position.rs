@@ -0,0 +1,62 @@
+use crate::alext::*;
+use crate::error::{check_al, Result};
+use crate::ext::require_al_extension;
+use crate::source::Source;
+use std::time::Duration;
+
+/// playback offset of a source together with the output latency, sampled atomically.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackPosition {
+    /// offset into the buffer queue in sample frames, including the fractional part.
+    pub samples: f64,
+    pub latency: Duration,
+}
+
+/// like `PlaybackPosition`, with the offset in seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackTime {
+    pub seconds: f64,
+    pub latency: Duration,
+}
+
+// `AL_SAMPLE_OFFSET_LATENCY_SOFT` reports the offset as 32.32 fixed point.
+fn fixed_to_samples(v: ALint64SOFT) -> f64 {
+    v as f64 / (1u64 << 32) as f64
+}
+
+impl Source {
+    /// precise playback position (`AL_SOFT_source_latency`).
+    pub fn playback_position(&self) -> Result<PlaybackPosition> {
+        require_al_extension("AL_SOFT_source_latency\0")?;
+        let mut v = [0; 2];
+        unsafe { alGetSourcei64vSOFT(self.id(), AL_SAMPLE_OFFSET_LATENCY_SOFT as ALenum, v.as_mut_ptr()) };
+        check_al()?;
+        Ok(PlaybackPosition {
+            samples: fixed_to_samples(v[0]),
+            latency: Duration::from_nanos(v[1].max(0) as u64),
+        })
+    }
+
+    pub fn playback_time(&self) -> Result<PlaybackTime> {
+        require_al_extension("AL_SOFT_source_latency\0")?;
+        let mut v = [0.; 2];
+        unsafe { alGetSourcedvSOFT(self.id(), AL_SEC_OFFSET_LATENCY_SOFT as ALenum, v.as_mut_ptr()) };
+        check_al()?;
+        Ok(PlaybackTime {
+            seconds: v[0],
+            latency: Duration::from_secs_f64(v[1].max(0.)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixed_to_samples;
+
+    #[test]
+    fn fixed_point_offset() {
+        assert_eq!(fixed_to_samples(0), 0.);
+        assert_eq!(fixed_to_samples(3 << 32), 3.);
+        assert_eq!(fixed_to_samples((5 << 32) | (1 << 31)), 5.5);
+    }
+}
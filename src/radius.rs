@@ -0,0 +1,22 @@
+use crate::alext::*;
+use crate::error::{ensure, Result};
+use crate::ext::require_al_extension;
+use crate::source::Source;
+
+impl Source {
+    /// radius of the source in meters; sound spreads out as the listener gets within it
+    /// (`AL_EXT_SOURCE_RADIUS`).
+    pub fn radius(&self) -> Result<f32> {
+        require_al_extension("AL_EXT_SOURCE_RADIUS\0")?;
+        self.get_f(AL_SOURCE_RADIUS)
+    }
+
+    pub fn set_radius(&mut self, meters: f32) -> Result<()> {
+        require_al_extension("AL_EXT_SOURCE_RADIUS\0")?;
+        ensure(
+            meters.is_finite() && meters >= 0.,
+            "source radius must be finite and non-negative",
+        )?;
+        self.set_f(AL_SOURCE_RADIUS, meters)
+    }
+}
@@ -0,0 +1,96 @@
+use crate::alext::*;
+use crate::error::{check_al, ensure, Error, Result};
+use crate::ext::require_al_extension;
+use crate::source::Source;
+use std::ffi::CStr;
+
+/// a resampler of `AL_SOFT_source_resampler`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resampler {
+    pub index: usize,
+    pub name: String,
+    pub is_default: bool,
+}
+
+// openal-soft's config file names for its resamplers.
+const CONFIG_NAMES: &[(&str, &str)] = &[
+    ("point", "Nearest"),
+    ("linear", "Linear"),
+    ("cubic", "Cubic"),
+    ("fast_bsinc12", "11th order Sinc (fast)"),
+    ("bsinc12", "11th order Sinc"),
+    ("fast_bsinc24", "23rd order Sinc (fast)"),
+    ("bsinc24", "23rd order Sinc"),
+];
+
+// compares case-insensitively against both the display name and the config file name.
+fn name_matches(resampler: &str, name: &str) -> bool {
+    resampler.eq_ignore_ascii_case(name)
+        || CONFIG_NAMES
+            .iter()
+            .any(|&(config, display)| config.eq_ignore_ascii_case(name) && display == resampler)
+}
+
+fn resampler_count() -> Result<usize> {
+    require_al_extension("AL_SOFT_source_resampler\0")?;
+    let count = unsafe { alGetInteger(AL_NUM_RESAMPLERS_SOFT as ALenum) };
+    check_al()?;
+    Ok(count.max(0) as usize)
+}
+
+pub fn resamplers() -> Result<Vec<Resampler>> {
+    let count = resampler_count()?;
+    let default = unsafe { alGetInteger(AL_DEFAULT_RESAMPLER_SOFT as ALenum) };
+    check_al()?;
+    (0..count)
+        .map(|i| {
+            let name = unsafe { alGetStringiSOFT(AL_RESAMPLER_NAME_SOFT as ALenum, i as ALsizei) };
+            check_al()?;
+            Ok(Resampler {
+                index: i,
+                name: unsafe { CStr::from_ptr(name) }.to_string_lossy().into_owned(),
+                is_default: i as ALint == default,
+            })
+        })
+        .collect()
+}
+
+/// finds a resampler by its name, or by its config file name such as `"bsinc24"`.
+pub fn find_resampler(name: &str) -> Result<Resampler> {
+    resamplers()?
+        .into_iter()
+        .find(|r| name_matches(&r.name, name))
+        .ok_or(Error::InvalidArgument("no resampler with that name"))
+}
+
+impl Source {
+    /// index of the resampler the source uses.
+    pub fn resampler(&self) -> Result<usize> {
+        require_al_extension("AL_SOFT_source_resampler\0")?;
+        Ok(self.get_i(AL_SOURCE_RESAMPLER_SOFT)? as usize)
+    }
+
+    pub fn set_resampler(&mut self, index: usize) -> Result<()> {
+        ensure(index < resampler_count()?, "resampler index out of range")?;
+        self.set_i(AL_SOURCE_RESAMPLER_SOFT, index as ALint)
+    }
+
+    pub fn set_resampler_by_name(&mut self, name: &str) -> Result<()> {
+        let resampler = find_resampler(name)?;
+        self.set_resampler(resampler.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::name_matches;
+
+    #[test]
+    fn resampler_names() {
+        assert!(name_matches("Cubic", "cubic"));
+        assert!(name_matches("23rd order Sinc", "bsinc24"));
+        assert!(name_matches("23rd order Sinc (fast)", "fast_bsinc24"));
+        assert!(!name_matches("23rd order Sinc (fast)", "bsinc24"));
+        assert!(!name_matches("Linear", "point"));
+    }
+}
@@ -0,0 +1,279 @@
+use crate::alext::*;
+use crate::buffer::Buffer;
+use crate::error::{check_al, Error, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceState {
+    Initial,
+    Playing,
+    Paused,
+    Stopped,
+}
+
+impl SourceState {
+    pub(crate) fn from_raw(v: ALint) -> Result<Self> {
+        Ok(match v as u32 {
+            AL_INITIAL => SourceState::Initial,
+            AL_PLAYING => SourceState::Playing,
+            AL_PAUSED => SourceState::Paused,
+            AL_STOPPED => SourceState::Stopped,
+            _ => return Err(Error::UnknownValue(v)),
+        })
+    }
+}
+
+/// owned OpenAL source object on the current context.
+#[derive(Debug)]
+pub struct Source {
+    id: ALuint,
+}
+
+impl Source {
+    pub fn new() -> Result<Self> {
+        let mut id = 0;
+        unsafe { alGenSources(1, &mut id) };
+        check_al()?;
+        Ok(Source { id })
+    }
+
+    pub fn id(&self) -> ALuint {
+        self.id
+    }
+
+    pub(crate) fn set_f(&mut self, param: u32, v: f32) -> Result<()> {
+        unsafe { alSourcef(self.id, param as ALenum, v) };
+        check_al()
+    }
+
+    pub(crate) fn get_f(&self, param: u32) -> Result<f32> {
+        let mut v = 0.;
+        unsafe { alGetSourcef(self.id, param as ALenum, &mut v) };
+        check_al()?;
+        Ok(v)
+    }
+
+    pub(crate) fn set_3f(&mut self, param: u32, v: [f32; 3]) -> Result<()> {
+        unsafe { alSource3f(self.id, param as ALenum, v[0], v[1], v[2]) };
+        check_al()
+    }
+
+    pub(crate) fn get_3f(&self, param: u32) -> Result<[f32; 3]> {
+        let mut v = [0.; 3];
+        unsafe { alGetSourcefv(self.id, param as ALenum, v.as_mut_ptr()) };
+        check_al()?;
+        Ok(v)
+    }
+
+    pub(crate) fn set_i(&mut self, param: u32, v: ALint) -> Result<()> {
+        unsafe { alSourcei(self.id, param as ALenum, v) };
+        check_al()
+    }
+
+    pub(crate) fn get_i(&self, param: u32) -> Result<ALint> {
+        let mut v = 0;
+        unsafe { alGetSourcei(self.id, param as ALenum, &mut v) };
+        check_al()?;
+        Ok(v)
+    }
+
+    pub fn gain(&self) -> Result<f32> {
+        self.get_f(AL_GAIN)
+    }
+
+    pub fn set_gain(&mut self, gain: f32) -> Result<()> {
+        self.set_f(AL_GAIN, gain)
+    }
+
+    pub fn pitch(&self) -> Result<f32> {
+        self.get_f(AL_PITCH)
+    }
+
+    pub fn set_pitch(&mut self, pitch: f32) -> Result<()> {
+        self.set_f(AL_PITCH, pitch)
+    }
+
+    pub fn position(&self) -> Result<[f32; 3]> {
+        self.get_3f(AL_POSITION)
+    }
+
+    pub fn set_position(&mut self, position: [f32; 3]) -> Result<()> {
+        self.set_3f(AL_POSITION, position)
+    }
+
+    pub fn velocity(&self) -> Result<[f32; 3]> {
+        self.get_3f(AL_VELOCITY)
+    }
+
+    pub fn set_velocity(&mut self, velocity: [f32; 3]) -> Result<()> {
+        self.set_3f(AL_VELOCITY, velocity)
+    }
+
+    pub fn direction(&self) -> Result<[f32; 3]> {
+        self.get_3f(AL_DIRECTION)
+    }
+
+    pub fn set_direction(&mut self, direction: [f32; 3]) -> Result<()> {
+        self.set_3f(AL_DIRECTION, direction)
+    }
+
+    /// inner cone angle in degrees.
+    pub fn cone_inner_angle(&self) -> Result<f32> {
+        self.get_f(AL_CONE_INNER_ANGLE)
+    }
+
+    pub fn set_cone_inner_angle(&mut self, degrees: f32) -> Result<()> {
+        self.set_f(AL_CONE_INNER_ANGLE, degrees)
+    }
+
+    /// outer cone angle in degrees.
+    pub fn cone_outer_angle(&self) -> Result<f32> {
+        self.get_f(AL_CONE_OUTER_ANGLE)
+    }
+
+    pub fn set_cone_outer_angle(&mut self, degrees: f32) -> Result<()> {
+        self.set_f(AL_CONE_OUTER_ANGLE, degrees)
+    }
+
+    pub fn cone_outer_gain(&self) -> Result<f32> {
+        self.get_f(AL_CONE_OUTER_GAIN)
+    }
+
+    pub fn set_cone_outer_gain(&mut self, gain: f32) -> Result<()> {
+        self.set_f(AL_CONE_OUTER_GAIN, gain)
+    }
+
+    pub fn reference_distance(&self) -> Result<f32> {
+        self.get_f(AL_REFERENCE_DISTANCE)
+    }
+
+    pub fn set_reference_distance(&mut self, distance: f32) -> Result<()> {
+        self.set_f(AL_REFERENCE_DISTANCE, distance)
+    }
+
+    pub fn rolloff_factor(&self) -> Result<f32> {
+        self.get_f(AL_ROLLOFF_FACTOR)
+    }
+
+    pub fn set_rolloff_factor(&mut self, factor: f32) -> Result<()> {
+        self.set_f(AL_ROLLOFF_FACTOR, factor)
+    }
+
+    pub fn max_distance(&self) -> Result<f32> {
+        self.get_f(AL_MAX_DISTANCE)
+    }
+
+    pub fn set_max_distance(&mut self, distance: f32) -> Result<()> {
+        self.set_f(AL_MAX_DISTANCE, distance)
+    }
+
+    pub fn looping(&self) -> Result<bool> {
+        Ok(self.get_i(AL_LOOPING)? != AL_FALSE as ALint)
+    }
+
+    pub fn set_looping(&mut self, looping: bool) -> Result<()> {
+        self.set_i(AL_LOOPING, if looping { AL_TRUE } else { AL_FALSE } as ALint)
+    }
+
+    /// whether position, velocity and direction are relative to the listener.
+    pub fn relative(&self) -> Result<bool> {
+        Ok(self.get_i(AL_SOURCE_RELATIVE)? != AL_FALSE as ALint)
+    }
+
+    pub fn set_relative(&mut self, relative: bool) -> Result<()> {
+        self.set_i(AL_SOURCE_RELATIVE, if relative { AL_TRUE } else { AL_FALSE } as ALint)
+    }
+
+    /// id of the attached buffer, `None` if no buffer is attached.
+    pub fn buffer(&self) -> Result<Option<ALuint>> {
+        let id = self.get_i(AL_BUFFER)? as ALuint;
+        Ok(if id == 0 { None } else { Some(id) })
+    }
+
+    /// attaches `buffer`, or detaches the current one with `None`.
+    ///
+    /// the source must be stopped or initial.
+    pub fn set_buffer(&mut self, buffer: Option<&Buffer>) -> Result<()> {
+        self.set_i(AL_BUFFER, buffer.map_or(0, Buffer::id) as ALint)
+    }
+
+    pub fn buffers_queued(&self) -> Result<usize> {
+        Ok(self.get_i(AL_BUFFERS_QUEUED)? as usize)
+    }
+
+    pub fn buffers_processed(&self) -> Result<usize> {
+        Ok(self.get_i(AL_BUFFERS_PROCESSED)? as usize)
+    }
+
+    /// appends `buffers` to the queue of a streaming source.
+    ///
+    /// all queued buffers must have the same format.
+    pub fn queue_buffers(&mut self, buffers: &[&Buffer]) -> Result<()> {
+        let ids: Vec<ALuint> = buffers.iter().map(|b| b.id()).collect();
+        unsafe { alSourceQueueBuffers(self.id, ids.len() as ALsizei, ids.as_ptr()) };
+        check_al()
+    }
+
+    /// removes `count` processed buffers from the queue, returning their ids.
+    pub fn unqueue_buffers(&mut self, count: usize) -> Result<Vec<ALuint>> {
+        let mut ids = vec![0; count];
+        unsafe { alSourceUnqueueBuffers(self.id, count as ALsizei, ids.as_mut_ptr()) };
+        check_al()?;
+        Ok(ids)
+    }
+
+    pub fn state(&self) -> Result<SourceState> {
+        SourceState::from_raw(self.get_i(AL_SOURCE_STATE)?)
+    }
+
+    pub fn play(&self) -> Result<()> {
+        unsafe { alSourcePlay(self.id) };
+        check_al()
+    }
+
+    pub fn pause(&self) -> Result<()> {
+        unsafe { alSourcePause(self.id) };
+        check_al()
+    }
+
+    pub fn stop(&self) -> Result<()> {
+        unsafe { alSourceStop(self.id) };
+        check_al()
+    }
+
+    pub fn rewind(&self) -> Result<()> {
+        unsafe { alSourceRewind(self.id) };
+        check_al()
+    }
+}
+
+impl Drop for Source {
+    fn drop(&mut self) {
+        unsafe { alDeleteSources(1, &self.id) };
+        let _ = check_al();
+    }
+}
+
+fn ids(sources: &[&Source]) -> Vec<ALuint> {
+    sources.iter().map(|s| s.id).collect()
+}
+
+/// starts all `sources` at the same time with `alSourcePlayv`.
+pub fn play_all(sources: &[&Source]) -> Result<()> {
+    let ids = ids(sources);
+    unsafe { alSourcePlayv(ids.len() as ALsizei, ids.as_ptr()) };
+    check_al()
+}
+
+/// stops all `sources` at the same time with `alSourceStopv`.
+pub fn stop_all(sources: &[&Source]) -> Result<()> {
+    let ids = ids(sources);
+    unsafe { alSourceStopv(ids.len() as ALsizei, ids.as_ptr()) };
+    check_al()
+}
+
+/// pauses all `sources` at the same time with `alSourcePausev`.
+pub fn pause_all(sources: &[&Source]) -> Result<()> {
+    let ids = ids(sources);
+    unsafe { alSourcePausev(ids.len() as ALsizei, ids.as_ptr()) };
+    check_al()
+}
@@ -0,0 +1,44 @@
+use crate::alext::*;
+use crate::error::{Error, Result};
+use crate::ext::require_al_extension;
+use crate::source::Source;
+
+/// whether a source is panned and attenuated by its position (`AL_SOFT_source_spatialize`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Spatialize {
+    Off,
+    On,
+    /// spatialize mono buffers only; the default.
+    Auto,
+}
+
+impl Spatialize {
+    pub(crate) fn to_raw(self) -> ALint {
+        (match self {
+            Spatialize::Off => AL_FALSE,
+            Spatialize::On => AL_TRUE,
+            Spatialize::Auto => AL_AUTO_SOFT,
+        }) as ALint
+    }
+
+    pub(crate) fn from_raw(v: ALint) -> Result<Self> {
+        Ok(match v as u32 {
+            AL_FALSE => Spatialize::Off,
+            AL_TRUE => Spatialize::On,
+            AL_AUTO_SOFT => Spatialize::Auto,
+            _ => return Err(Error::UnknownValue(v)),
+        })
+    }
+}
+
+impl Source {
+    pub fn spatialize(&self) -> Result<Spatialize> {
+        require_al_extension("AL_SOFT_source_spatialize\0")?;
+        Spatialize::from_raw(self.get_i(AL_SOURCE_SPATIALIZE_SOFT)?)
+    }
+
+    pub fn set_spatialize(&mut self, mode: Spatialize) -> Result<()> {
+        require_al_extension("AL_SOFT_source_spatialize\0")?;
+        self.set_i(AL_SOURCE_SPATIALIZE_SOFT, mode.to_raw())
+    }
+}
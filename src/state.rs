@@ -0,0 +1,82 @@
+use crate::alext::*;
+use crate::error::{check_al, ensure, Error, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceModel {
+    None,
+    Inverse,
+    InverseClamped,
+    Linear,
+    LinearClamped,
+    Exponent,
+    ExponentClamped,
+}
+
+impl DistanceModel {
+    pub(crate) fn to_raw(self) -> ALenum {
+        (match self {
+            DistanceModel::None => AL_NONE,
+            DistanceModel::Inverse => AL_INVERSE_DISTANCE,
+            DistanceModel::InverseClamped => AL_INVERSE_DISTANCE_CLAMPED,
+            DistanceModel::Linear => AL_LINEAR_DISTANCE,
+            DistanceModel::LinearClamped => AL_LINEAR_DISTANCE_CLAMPED,
+            DistanceModel::Exponent => AL_EXPONENT_DISTANCE,
+            DistanceModel::ExponentClamped => AL_EXPONENT_DISTANCE_CLAMPED,
+        }) as ALenum
+    }
+
+    pub(crate) fn from_raw(v: ALint) -> Result<Self> {
+        Ok(match v as u32 {
+            AL_NONE => DistanceModel::None,
+            AL_INVERSE_DISTANCE => DistanceModel::Inverse,
+            AL_INVERSE_DISTANCE_CLAMPED => DistanceModel::InverseClamped,
+            AL_LINEAR_DISTANCE => DistanceModel::Linear,
+            AL_LINEAR_DISTANCE_CLAMPED => DistanceModel::LinearClamped,
+            AL_EXPONENT_DISTANCE => DistanceModel::Exponent,
+            AL_EXPONENT_DISTANCE_CLAMPED => DistanceModel::ExponentClamped,
+            _ => return Err(Error::UnknownValue(v)),
+        })
+    }
+}
+
+pub fn distance_model() -> Result<DistanceModel> {
+    let v = unsafe { alGetInteger(AL_DISTANCE_MODEL as ALenum) };
+    check_al()?;
+    DistanceModel::from_raw(v)
+}
+
+pub fn set_distance_model(model: DistanceModel) -> Result<()> {
+    unsafe { alDistanceModel(model.to_raw()) };
+    check_al()
+}
+
+pub fn doppler_factor() -> Result<f32> {
+    let v = unsafe { alGetFloat(AL_DOPPLER_FACTOR as ALenum) };
+    check_al()?;
+    Ok(v)
+}
+
+pub fn set_doppler_factor(factor: f32) -> Result<()> {
+    ensure(
+        factor.is_finite() && factor >= 0.,
+        "doppler factor must be finite and non-negative",
+    )?;
+    unsafe { alDopplerFactor(factor) };
+    check_al()
+}
+
+/// speed of sound in units per second.
+pub fn speed_of_sound() -> Result<f32> {
+    let v = unsafe { alGetFloat(AL_SPEED_OF_SOUND as ALenum) };
+    check_al()?;
+    Ok(v)
+}
+
+pub fn set_speed_of_sound(speed: f32) -> Result<()> {
+    ensure(
+        speed.is_finite() && speed > 0.,
+        "speed of sound must be finite and positive",
+    )?;
+    unsafe { alSpeedOfSound(speed) };
+    check_al()
+}
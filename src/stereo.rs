@@ -0,0 +1,38 @@
+use crate::alext::*;
+use crate::error::{check_al, ensure, Result};
+use crate::ext::require_al_extension;
+use crate::source::Source;
+use std::f32::consts::PI;
+
+impl Source {
+    /// angles of the left and right channels of a stereo buffer, in radians counter-clockwise from
+    /// straight ahead (`AL_EXT_STEREO_ANGLES`). the default is `[π/6, -π/6]`.
+    pub fn stereo_angles(&self) -> Result<[f32; 2]> {
+        require_al_extension("AL_EXT_STEREO_ANGLES\0")?;
+        let mut v = [0.; 2];
+        unsafe { alGetSourcefv(self.id(), AL_STEREO_ANGLES as ALenum, v.as_mut_ptr()) };
+        check_al()?;
+        Ok(v)
+    }
+
+    /// angles must be within `[-π, π]`.
+    pub fn set_stereo_angles(&mut self, left: f32, right: f32) -> Result<()> {
+        require_al_extension("AL_EXT_STEREO_ANGLES\0")?;
+        ensure(
+            left.abs() <= PI && right.abs() <= PI,
+            "stereo angles must be within [-pi, pi] radians",
+        )?;
+        let v = [left, right];
+        unsafe { alSourcefv(self.id(), AL_STEREO_ANGLES as ALenum, v.as_ptr()) };
+        check_al()
+    }
+
+    pub fn stereo_angles_degrees(&self) -> Result<[f32; 2]> {
+        let [left, right] = self.stereo_angles()?;
+        Ok([left.to_degrees(), right.to_degrees()])
+    }
+
+    pub fn set_stereo_angles_degrees(&mut self, left: f32, right: f32) -> Result<()> {
+        self.set_stereo_angles(left.to_radians(), right.to_radians())
+    }
+}
@@ -0,0 +1,160 @@
+use crate::alext::*;
+use crate::buffer::Buffer;
+use crate::error::{ensure, Result};
+use crate::format::{Channels, Sample};
+use crate::source::{Source, SourceState};
+
+/// a source fed by a producer closure through a ring of queued buffers.
+///
+/// the producer fills the given slice with interleaved samples and returns how many it wrote;
+/// returning 0 ends the stream. call `update` regularly, e.g. once per frame.
+pub struct StreamingSource<T: Sample, F: FnMut(&mut [T]) -> usize> {
+    // declared before `buffers`: deleting the source first releases the queued buffers.
+    source: Source,
+    buffers: Vec<Buffer>,
+    // indices of buffers not on the queue, refilled by `play` and `update`.
+    free: Vec<usize>,
+    channels: Channels,
+    frequency: ALsizei,
+    producer: F,
+    scratch: Vec<T>,
+    playing: bool,
+    exhausted: bool,
+    underruns: usize,
+}
+
+impl<T: Sample, F: FnMut(&mut [T]) -> usize> StreamingSource<T, F> {
+    /// keeps `buffer_count` buffers of `frames_per_buffer` frames each in flight.
+    pub fn new(
+        source: Source,
+        channels: Channels,
+        frequency: ALsizei,
+        buffer_count: usize,
+        frames_per_buffer: usize,
+        producer: F,
+    ) -> Result<Self> {
+        ensure(buffer_count >= 2, "a streaming source needs at least 2 buffers")?;
+        ensure(frames_per_buffer > 0, "frames per buffer must be positive")?;
+        let buffers = (0..buffer_count).map(|_| Buffer::new()).collect::<Result<Vec<_>>>()?;
+        Ok(StreamingSource {
+            source,
+            free: (0..buffer_count).rev().collect(),
+            buffers,
+            channels,
+            frequency,
+            producer,
+            scratch: vec![T::default(); frames_per_buffer * channels.count()],
+            playing: false,
+            exhausted: false,
+            underruns: 0,
+        })
+    }
+
+    pub fn source(&self) -> &Source {
+        &self.source
+    }
+
+    /// queueing buffers of your own on the source makes `update` fail.
+    pub fn source_mut(&mut self) -> &mut Source {
+        &mut self.source
+    }
+
+    /// number of times playback starved and was restarted by `update`.
+    pub fn underruns(&self) -> usize {
+        self.underruns
+    }
+
+    /// `true` once the producer has ended and every queued buffer has been played.
+    pub fn is_finished(&self) -> Result<bool> {
+        Ok(self.exhausted && self.source.buffers_queued()? == 0)
+    }
+
+    // fills the buffer at `index` from the producer; `false` when the producer has ended.
+    fn fill(&mut self, index: usize) -> Result<bool> {
+        if self.exhausted {
+            return Ok(false);
+        }
+        let channels = self.channels.count();
+        let written = (self.producer)(&mut self.scratch).min(self.scratch.len());
+        // drop any trailing partial frame.
+        let written = written - written % channels;
+        if written == 0 {
+            self.exhausted = true;
+            return Ok(false);
+        }
+        self.buffers[index].data(self.channels, &self.scratch[..written], self.frequency)?;
+        self.source.queue_buffers(&[&self.buffers[index]])?;
+        Ok(true)
+    }
+
+    // queues free buffers until they run out or the producer ends. a buffer stays free if filling
+    // it fails, so the next call retries it.
+    fn refill(&mut self) -> Result<()> {
+        while let Some(&index) = self.free.last() {
+            if !self.fill(index)? {
+                break;
+            }
+            self.free.pop();
+        }
+        Ok(())
+    }
+
+    // returns unqueued buffers to the free list; `false` if any of them isn't ours.
+    fn release(&mut self, ids: &[ALuint]) -> bool {
+        let mut own = true;
+        for &id in ids {
+            match self.buffers.iter().position(|b| b.id() == id) {
+                Some(index) => self.free.push(index),
+                None => own = false,
+            }
+        }
+        own
+    }
+
+    /// queues every free buffer and starts playback.
+    pub fn play(&mut self) -> Result<()> {
+        self.refill()?;
+        self.source.play()?;
+        self.playing = true;
+        Ok(())
+    }
+
+    pub fn pause(&mut self) -> Result<()> {
+        self.source.pause()?;
+        self.playing = false;
+        Ok(())
+    }
+
+    /// stops playback and drops every queued buffer; the producer is not rewound.
+    pub fn stop(&mut self) -> Result<()> {
+        self.playing = false;
+        self.source.stop()?;
+        let queued = self.source.buffers_queued()?;
+        let ids = self.source.unqueue_buffers(queued)?;
+        self.release(&ids);
+        Ok(())
+    }
+
+    /// refills processed buffers and restarts playback after an underrun.
+    ///
+    /// fails with `Error::InvalidArgument` if a buffer not owned by the stream was queued on the
+    /// source; it is unqueued and the stream carries on with its own buffers.
+    pub fn update(&mut self) -> Result<()> {
+        let processed = self.source.buffers_processed()?;
+        let ids = self.source.unqueue_buffers(processed)?;
+        let own = self.release(&ids);
+        self.refill()?;
+        ensure(own, "a buffer not owned by the streaming source was queued on it")?;
+
+        // the source stops by itself when it runs out of queued data.
+        if self.playing && self.source.state()? == SourceState::Stopped {
+            if self.source.buffers_queued()? > 0 {
+                self.underruns += 1;
+                self.source.play()?;
+            } else if self.exhausted {
+                self.playing = false;
+            }
+        }
+        Ok(())
+    }
+}